@@ -0,0 +1,83 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+pub async fn assert_eventually<F, Fut>(timeout: Duration, interval: Duration, check: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    assert_eventually_matches(timeout, interval, check, |met| *met).await;
+}
+
+pub async fn assert_eventually_matches<T, F, Fut, P>(
+    timeout: Duration,
+    interval: Duration,
+    mut probe: F,
+    predicate: P,
+) -> T
+where
+    T: Debug,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+    P: Fn(&T) -> bool,
+{
+    let deadline = Instant::now() + timeout;
+    let mut attempts = 0;
+
+    loop {
+        let value = probe().await;
+        attempts += 1;
+        if predicate(&value) {
+            return value;
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        assert!(
+            !remaining.is_zero(),
+            "Condition not met within {timeout:?} after {attempts} attempts; last observed value: {value:?}"
+        );
+        sleep(interval.min(remaining)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_eventually_returns_once_condition_holds() {
+        let polls = AtomicUsize::new(0);
+
+        let observed = assert_eventually_matches(
+            Duration::from_secs(5),
+            Duration::from_millis(10),
+            || async { polls.fetch_add(1, Ordering::SeqCst) + 1 },
+            |count| *count >= 3,
+        )
+        .await;
+        assert_eq!(observed, 3);
+
+        assert_eventually(
+            Duration::from_secs(1),
+            Duration::from_millis(10),
+            || async { polls.load(Ordering::SeqCst) == 3 },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "after 3 attempts; last observed value: \"pending\"")]
+    async fn test_eventually_reports_last_value_on_timeout() {
+        tokio::time::pause();
+        assert_eventually_matches(
+            Duration::from_millis(100),
+            Duration::from_millis(50),
+            || async { "pending" },
+            |status| *status == "delivered",
+        )
+        .await;
+    }
+}
@@ -0,0 +1,5 @@
+mod eventually;
+mod soft;
+
+pub use eventually::{assert_eventually, assert_eventually_matches};
+pub use soft::SoftAssert;
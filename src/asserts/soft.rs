@@ -0,0 +1,122 @@
+use crate::bot::AssertionResult;
+use std::fmt::Debug;
+
+#[derive(Debug, Default)]
+pub struct SoftAssert {
+    results: Vec<AssertionResult>,
+    finished: bool,
+}
+
+impl SoftAssert {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, result: AssertionResult) -> bool {
+        let passed = result.passed;
+        self.results.push(result);
+        passed
+    }
+
+    pub fn check(&mut self, condition: bool, message: &str) -> bool {
+        if condition {
+            self.record(AssertionResult::pass(message))
+        } else {
+            self.record(AssertionResult::fail(message, "true", "false"))
+        }
+    }
+
+    pub fn check_eq<T>(&mut self, actual: T, expected: T, message: &str) -> bool
+    where
+        T: PartialEq + Debug,
+    {
+        if actual == expected {
+            self.record(AssertionResult::pass(message))
+        } else {
+            self.record(AssertionResult::fail(
+                message,
+                &format!("{expected:?}"),
+                &format!("{actual:?}"),
+            ))
+        }
+    }
+
+    #[must_use]
+    pub fn results(&self) -> &[AssertionResult] {
+        &self.results
+    }
+
+    #[must_use]
+    pub fn failures(&self) -> Vec<&AssertionResult> {
+        self.results.iter().filter(|r| !r.passed).collect()
+    }
+
+    #[must_use]
+    pub fn report(&self) -> Option<String> {
+        let failures = self.failures();
+        if failures.is_empty() {
+            return None;
+        }
+
+        let mut report = format!(
+            "{} of {} soft assertions failed:",
+            failures.len(),
+            self.results.len()
+        );
+        for failure in failures {
+            report.push_str(&format!("\n  - {}", failure.message));
+            if let (Some(expected), Some(actual)) = (&failure.expected, &failure.actual) {
+                report.push_str(&format!(" (expected {expected}, got {actual})"));
+            }
+        }
+        Some(report)
+    }
+
+    pub fn finish(mut self) {
+        self.finished = true;
+        if let Some(report) = self.report() {
+            panic!("{report}");
+        }
+    }
+}
+
+impl Drop for SoftAssert {
+    fn drop(&mut self) {
+        if self.finished || std::thread::panicking() {
+            return;
+        }
+        if let Some(report) = self.report() {
+            panic!("{report}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soft_assert_reports_all_failures_once() {
+        let mut soft = SoftAssert::new();
+        assert!(soft.check(1 + 1 == 2, "arithmetic works"));
+        assert!(!soft.check_eq("queued", "delivered", "message status"));
+        assert!(soft.check_eq(3, 3, "reply count"));
+
+        assert_eq!(soft.results().len(), 3);
+        assert_eq!(
+            soft.report().unwrap(),
+            "1 of 3 soft assertions failed:\n  - message status (expected \"delivered\", got \"queued\")"
+        );
+
+        let panic =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| soft.finish())).unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(message.starts_with("1 of 3 soft assertions failed"));
+
+        let mut clean = SoftAssert::new();
+        clean.check(true, "always");
+        assert!(clean.report().is_none());
+        clean.finish();
+    }
+}
@@ -0,0 +1,343 @@
+use super::{AssertionRecord, AssertionResult, ConversationRecord, RecordedMessage};
+use crate::fixtures::{MessageDirection, QueueEntry, QueueStatus, Session, SessionState};
+use anyhow::Result;
+
+pub struct ConversationAssertions<'a> {
+    messages: &'a [RecordedMessage],
+    session: Option<&'a Session>,
+    queue: &'a [QueueEntry],
+    records: Vec<AssertionRecord>,
+}
+
+impl<'a> ConversationAssertions<'a> {
+    #[must_use]
+    pub const fn new(messages: &'a [RecordedMessage]) -> Self {
+        Self {
+            messages,
+            session: None,
+            queue: &[],
+            records: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_session(mut self, session: &'a Session) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_queue(mut self, queue: &'a [QueueEntry]) -> Self {
+        self.queue = queue;
+        self
+    }
+
+    #[must_use]
+    pub fn for_record(record: &'a ConversationRecord) -> Self {
+        Self::new(&record.messages)
+    }
+
+    pub fn assert_reply(&mut self) -> ReplyAssertion<'_, 'a> {
+        let reply = self
+            .messages
+            .iter()
+            .rev()
+            .find(|m| m.direction == MessageDirection::Outgoing);
+        ReplyAssertion { owner: self, reply }
+    }
+
+    pub fn assert_reply_at(&mut self, index: usize) -> ReplyAssertion<'_, 'a> {
+        let reply = self
+            .messages
+            .iter()
+            .filter(|m| m.direction == MessageDirection::Outgoing)
+            .nth(index);
+        ReplyAssertion { owner: self, reply }
+    }
+
+    #[must_use]
+    pub fn records(&self) -> &[AssertionRecord] {
+        &self.records
+    }
+
+    #[must_use]
+    pub fn failures(&self) -> Vec<&AssertionRecord> {
+        self.records.iter().filter(|r| !r.passed).collect()
+    }
+
+    #[must_use]
+    pub fn all_passed(&self) -> bool {
+        self.records.iter().all(|r| r.passed)
+    }
+
+    pub fn verify(&self) -> Result<()> {
+        let failures = self.failures();
+        if failures.is_empty() {
+            return Ok(());
+        }
+
+        let details: Vec<String> = failures
+            .iter()
+            .map(|f| format!("  [{}] {}", f.assertion_type, f.message))
+            .collect();
+        anyhow::bail!(
+            "{} of {} conversation assertions failed:\n{}",
+            failures.len(),
+            self.records.len(),
+            details.join("\n")
+        )
+    }
+
+    #[must_use]
+    pub fn into_records(self) -> Vec<AssertionRecord> {
+        self.records
+    }
+
+    fn handoff(&self) -> Option<String> {
+        let session = self.session?;
+        if session.state == SessionState::Transferred {
+            return Some("session transferred".to_string());
+        }
+
+        self.queue
+            .iter()
+            .find(|entry| entry.session_id == session.id && entry.status != QueueStatus::Cancelled)
+            .map(|entry| format!("queue entry {:?}", entry.status))
+    }
+
+    fn push(&mut self, assertion_type: &str, result: &AssertionResult) {
+        self.records
+            .push(AssertionRecord::from_result(assertion_type, result));
+    }
+}
+
+pub struct ReplyAssertion<'s, 'a> {
+    owner: &'s mut ConversationAssertions<'a>,
+    reply: Option<&'a RecordedMessage>,
+}
+
+impl ReplyAssertion<'_, '_> {
+    pub fn contains(&mut self, text: &str) -> &mut Self {
+        let result = match self.reply {
+            Some(reply) if reply.content.contains(text) => {
+                AssertionResult::pass(&format!("Reply contains '{text}'"))
+            }
+            Some(reply) => AssertionResult::fail(
+                &format!("Reply should contain '{text}'"),
+                text,
+                &reply.content,
+            ),
+            None => AssertionResult::fail("No bot reply recorded", text, "<no reply>"),
+        };
+        self.record("contains", &result)
+    }
+
+    pub fn matches_regex(&mut self, pattern: &str) -> &mut Self {
+        let result = match (regex::Regex::new(pattern), self.reply) {
+            (Err(e), _) => AssertionResult::fail(
+                &format!("Invalid regex pattern: {e}"),
+                pattern,
+                "<invalid pattern>",
+            ),
+            (Ok(re), Some(reply)) if re.is_match(&reply.content) => {
+                AssertionResult::pass(&format!("Reply matches '{pattern}'"))
+            }
+            (Ok(_), Some(reply)) => AssertionResult::fail(
+                &format!("Reply should match '{pattern}'"),
+                pattern,
+                &reply.content,
+            ),
+            (Ok(_), None) => AssertionResult::fail("No bot reply recorded", pattern, "<no reply>"),
+        };
+        self.record("matches_regex", &result)
+    }
+
+    pub fn has_quick_reply(&mut self, label: &str) -> &mut Self {
+        let result = match self.reply {
+            Some(reply) if reply.quick_replies.iter().any(|q| q == label) => {
+                AssertionResult::pass(&format!("Reply offers quick reply '{label}'"))
+            }
+            Some(reply) => AssertionResult::fail(
+                &format!("Reply should offer quick reply '{label}'"),
+                label,
+                &reply.quick_replies.join(", "),
+            ),
+            None => AssertionResult::fail("No bot reply recorded", label, "<no reply>"),
+        };
+        self.record("has_quick_reply", &result)
+    }
+
+    pub fn transferred_to_human(&mut self) -> &mut Self {
+        let result = match (self.owner.session, self.owner.handoff()) {
+            (_, Some(signal)) => {
+                AssertionResult::pass(&format!("Conversation transferred to human ({signal})"))
+            }
+            (Some(session), None) => AssertionResult::fail(
+                "Should be transferred to human",
+                "transferred session or queue entry",
+                &format!("session {:?} with no queue entry", session.state),
+            ),
+            (None, None) => AssertionResult::fail(
+                "No session recorded to check for a handoff",
+                "transferred session or queue entry",
+                "<no session>",
+            ),
+        };
+        self.record("transferred", &result)
+    }
+
+    pub fn within_latency(&mut self, max_ms: u64) -> &mut Self {
+        let result = match self.reply.and_then(|r| r.latency_ms) {
+            Some(latency) if latency <= max_ms => {
+                AssertionResult::pass(&format!("Reply took {latency}ms (max {max_ms}ms)"))
+            }
+            Some(latency) => AssertionResult::fail(
+                &format!("Reply should arrive within {max_ms}ms"),
+                &format!("<= {max_ms}ms"),
+                &format!("{latency}ms"),
+            ),
+            None => AssertionResult::fail(
+                "No reply latency recorded",
+                &format!("<= {max_ms}ms"),
+                "<unknown>",
+            ),
+        };
+        self.record("within_latency", &result)
+    }
+
+    fn record(&mut self, assertion_type: &str, result: &AssertionResult) -> &mut Self {
+        self.owner.push(assertion_type, result);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{queue_entry_for, Customer};
+    use chrono::Utc;
+
+    fn message(
+        direction: MessageDirection,
+        content: &str,
+        latency_ms: Option<u64>,
+    ) -> RecordedMessage {
+        RecordedMessage {
+            timestamp: Utc::now(),
+            direction,
+            content: content.to_string(),
+            latency_ms,
+            quick_replies: Vec::new(),
+            transferred: false,
+        }
+    }
+
+    fn transcript() -> Vec<RecordedMessage> {
+        let mut menu = message(
+            MessageDirection::Outgoing,
+            "Welcome! Order #1234 received.",
+            Some(120),
+        );
+        menu.quick_replies = vec!["Track order".to_string(), "Talk to us".to_string()];
+
+        let handoff = message(MessageDirection::Outgoing, "One moment please", Some(900));
+
+        vec![
+            message(MessageDirection::Incoming, "hi", None),
+            menu,
+            message(MessageDirection::Incoming, "Talk to us", None),
+            handoff,
+        ]
+    }
+
+    #[test]
+    fn test_passing_assertions() {
+        let messages = transcript();
+        let session = Session {
+            state: SessionState::Transferred,
+            ..Session::default()
+        };
+        let mut assertions = ConversationAssertions::new(&messages).with_session(&session);
+
+        assertions
+            .assert_reply_at(0)
+            .contains("Welcome")
+            .matches_regex(r"Order #\d+")
+            .has_quick_reply("Track order")
+            .within_latency(500);
+        assertions.assert_reply().transferred_to_human();
+
+        assert_eq!(assertions.records().len(), 5);
+        assert!(assertions.all_passed());
+        assert!(assertions.verify().is_ok());
+    }
+
+    #[test]
+    fn test_failures_accumulate() {
+        let messages = transcript();
+        let mut assertions = ConversationAssertions::new(&messages);
+
+        assertions
+            .assert_reply()
+            .contains("Goodbye")
+            .matches_regex("^One moment")
+            .has_quick_reply("Cancel")
+            .within_latency(500);
+
+        let failures = assertions.failures();
+        let types: Vec<&str> = failures.iter().map(|f| f.assertion_type.as_str()).collect();
+        assert_eq!(types, vec!["contains", "has_quick_reply", "within_latency"]);
+
+        let err = assertions.verify().unwrap_err().to_string();
+        assert!(err.starts_with("3 of 4 conversation assertions failed"));
+        assert!(err.contains("Goodbye"));
+        assert!(err.contains("Cancel"));
+        assert!(err.contains("900ms"));
+    }
+
+    #[test]
+    fn test_transfer_requires_queue_entry_or_session_status() {
+        let messages = vec![message(
+            MessageDirection::Outgoing,
+            "Let me transfer you to a human agent",
+            Some(100),
+        )];
+        let session = Session::default();
+        let mut queue = vec![queue_entry_for(&Customer::default(), &session)];
+
+        let mut assertions = ConversationAssertions::new(&messages);
+        assertions.assert_reply().transferred_to_human();
+        assert!(!assertions.all_passed());
+
+        let mut assertions = ConversationAssertions::new(&messages).with_session(&session);
+        assertions.assert_reply().transferred_to_human();
+        assert!(!assertions.all_passed());
+
+        let mut assertions = ConversationAssertions::new(&messages)
+            .with_session(&session)
+            .with_queue(&queue);
+        assertions.assert_reply().transferred_to_human();
+        assert!(assertions.all_passed());
+
+        queue[0].status = QueueStatus::Cancelled;
+        let mut assertions = ConversationAssertions::new(&messages)
+            .with_session(&session)
+            .with_queue(&queue);
+        assertions.assert_reply().transferred_to_human();
+        assert!(!assertions.all_passed());
+    }
+
+    #[test]
+    fn test_no_reply_fails() {
+        let messages = vec![message(MessageDirection::Incoming, "hello?", None)];
+        let mut assertions = ConversationAssertions::new(&messages);
+
+        assertions
+            .assert_reply()
+            .contains("hi")
+            .transferred_to_human();
+
+        assert_eq!(assertions.failures().len(), 2);
+        assert!(!assertions.all_passed());
+    }
+}
@@ -0,0 +1,268 @@
+use super::{AssertionRecord, AssertionResult, ConversationRecord, RecordedMessage};
+use crate::fixtures::MessageDirection;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default)]
+pub struct ChatReply {
+    pub content: String,
+    pub quick_replies: Vec<String>,
+    pub transferred: bool,
+    pub latency_ms: u64,
+}
+
+impl ChatReply {
+    fn from_json(json: &serde_json::Value, latency_ms: u64) -> Self {
+        let content = ["content", "message", "text", "reply"]
+            .iter()
+            .find_map(|key| json.get(key).and_then(|v| v.as_str()))
+            .unwrap_or_default()
+            .to_string();
+
+        let quick_replies = json
+            .get("quick_replies")
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| {
+                        item.as_str()
+                            .or_else(|| item.get("label").and_then(|l| l.as_str()))
+                            .or_else(|| item.get("title").and_then(|t| t.as_str()))
+                            .map(str::to_string)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let transferred = json
+            .get("transferred")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        Self {
+            content,
+            quick_replies,
+            transferred,
+            latency_ms,
+        }
+    }
+}
+
+pub struct ConversationRunner {
+    client: reqwest::Client,
+    base_url: String,
+    bot_name: String,
+    session_id: Uuid,
+    record: ConversationRecord,
+    last_reply: Option<ChatReply>,
+}
+
+impl ConversationRunner {
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+    #[must_use]
+    pub fn new(base_url: &str, bot_name: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Self::REQUEST_TIMEOUT)
+            .danger_accept_invalid_certs(true)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            bot_name: bot_name.to_string(),
+            session_id: Uuid::new_v4(),
+            record: ConversationRecord {
+                id: Uuid::new_v4(),
+                bot_name: bot_name.to_string(),
+                started_at: Utc::now(),
+                ended_at: None,
+                messages: Vec::new(),
+                assertions: Vec::new(),
+                passed: true,
+            },
+            last_reply: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn session_id(&self) -> Uuid {
+        self.session_id
+    }
+
+    #[must_use]
+    pub fn chat_url(&self) -> String {
+        format!("{}/api/bots/{}/chat", self.base_url, self.bot_name)
+    }
+
+    #[must_use]
+    pub const fn last_reply(&self) -> Option<&ChatReply> {
+        self.last_reply.as_ref()
+    }
+
+    #[must_use]
+    pub const fn record(&self) -> &ConversationRecord {
+        &self.record
+    }
+
+    pub async fn send(&mut self, text: &str) -> Result<&ChatReply> {
+        self.record.messages.push(RecordedMessage {
+            timestamp: Utc::now(),
+            direction: MessageDirection::Incoming,
+            content: text.to_string(),
+            latency_ms: None,
+            quick_replies: Vec::new(),
+            transferred: false,
+        });
+
+        let start = Instant::now();
+        let response = self
+            .client
+            .post(self.chat_url())
+            .json(&serde_json::json!({
+                "session_id": self.session_id,
+                "message": text,
+            }))
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach chat API at {}", self.chat_url()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Chat API returned {status} for '{text}'");
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .context("Chat API returned invalid JSON")?;
+        let latency_ms = start.elapsed().as_millis() as u64;
+        let reply = ChatReply::from_json(&json, latency_ms);
+
+        self.record.messages.push(RecordedMessage {
+            timestamp: Utc::now(),
+            direction: MessageDirection::Outgoing,
+            content: reply.content.clone(),
+            latency_ms: Some(latency_ms),
+            quick_replies: reply.quick_replies.clone(),
+            transferred: reply.transferred,
+        });
+
+        Ok(&*self.last_reply.insert(reply))
+    }
+
+    pub fn expect_reply_contains(&mut self, text: &str) -> &mut Self {
+        let result = match self.last_reply {
+            Some(ref reply) if reply.content.contains(text) => {
+                AssertionResult::pass(&format!("Reply contains '{text}'"))
+            }
+            Some(ref reply) => AssertionResult::fail(
+                &format!("Reply should contain '{text}'"),
+                text,
+                &reply.content,
+            ),
+            None => AssertionResult::fail("No reply to check", text, "<no reply>"),
+        };
+
+        self.push_assertion("reply_contains", &result);
+        self
+    }
+
+    pub fn expect_reply_equals(&mut self, text: &str) -> &mut Self {
+        let result = match self.last_reply {
+            Some(ref reply) if reply.content == text => {
+                AssertionResult::pass(&format!("Reply equals '{text}'"))
+            }
+            Some(ref reply) => AssertionResult::fail(
+                &format!("Reply should equal '{text}'"),
+                text,
+                &reply.content,
+            ),
+            None => AssertionResult::fail("No reply to check", text, "<no reply>"),
+        };
+
+        self.push_assertion("reply_equals", &result);
+        self
+    }
+
+    pub fn expect_quick_replies(&mut self, labels: &[&str]) -> &mut Self {
+        let expected = labels.join(", ");
+        let result = match self.last_reply {
+            Some(ref reply) => {
+                let missing: Vec<&str> = labels
+                    .iter()
+                    .copied()
+                    .filter(|label| !reply.quick_replies.iter().any(|q| q == label))
+                    .collect();
+                if missing.is_empty() {
+                    AssertionResult::pass(&format!("Quick replies include [{expected}]"))
+                } else {
+                    AssertionResult::fail(
+                        &format!("Quick replies missing [{}]", missing.join(", ")),
+                        &expected,
+                        &reply.quick_replies.join(", "),
+                    )
+                }
+            }
+            None => AssertionResult::fail("No reply to check", &expected, "<no reply>"),
+        };
+
+        self.push_assertion("quick_replies", &result);
+        self
+    }
+
+    #[must_use]
+    pub fn finish(mut self) -> ConversationRecord {
+        self.record.ended_at = Some(Utc::now());
+        self.record
+    }
+
+    fn push_assertion(&mut self, assertion_type: &str, result: &AssertionResult) {
+        if !result.passed {
+            self.record.passed = false;
+        }
+        self.record
+            .assertions
+            .push(AssertionRecord::from_result(assertion_type, result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_reply_from_json() {
+        let json = serde_json::json!({
+            "message": "Pick one",
+            "quick_replies": ["Sales", {"label": "Support"}, {"title": "Billing"}],
+            "transferred": true
+        });
+
+        let reply = ChatReply::from_json(&json, 42);
+        assert_eq!(reply.content, "Pick one");
+        assert_eq!(reply.quick_replies, vec!["Sales", "Support", "Billing"]);
+        assert!(reply.transferred);
+        assert_eq!(reply.latency_ms, 42);
+    }
+
+    #[test]
+    fn test_expectations_without_reply_fail() {
+        let mut runner = ConversationRunner::new("http://localhost:1/", "support");
+        assert_eq!(
+            runner.chat_url(),
+            "http://localhost:1/api/bots/support/chat"
+        );
+
+        runner.expect_reply_contains("hello");
+        let record = runner.finish();
+
+        assert!(!record.passed);
+        assert_eq!(record.assertions.len(), 1);
+        assert!(record.ended_at.is_some());
+    }
+}
@@ -186,6 +186,8 @@ impl ConversationTest {
                 direction: MessageDirection::Incoming,
                 content: message.to_string(),
                 latency_ms: None,
+                quick_replies: Vec::new(),
+                transferred: false,
             });
         }
 
@@ -205,6 +207,8 @@ impl ConversationTest {
                 direction: MessageDirection::Outgoing,
                 content: response.content,
                 latency_ms: Some(latency.as_millis() as u64),
+                quick_replies: Vec::new(),
+                transferred: false,
             });
         }
 
@@ -0,0 +1,165 @@
+use super::ConversationRecord;
+use crate::fixtures::MessageDirection;
+use anyhow::Result;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GoldenOptions {
+    pub normalize_whitespace: bool,
+}
+
+impl GoldenOptions {
+    #[must_use]
+    pub const fn normalize_whitespace(mut self) -> Self {
+        self.normalize_whitespace = true;
+        self
+    }
+
+    fn normalize(self, text: &str) -> String {
+        if self.normalize_whitespace {
+            text.split_whitespace().collect::<Vec<_>>().join(" ")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+impl ConversationRecord {
+    fn bot_replies(&self) -> Vec<&str> {
+        self.messages
+            .iter()
+            .filter(|m| m.direction == MessageDirection::Outgoing)
+            .map(|m| m.content.as_str())
+            .collect()
+    }
+
+    pub fn assert_matches_golden(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.assert_matches_golden_with(path, GoldenOptions::default())
+    }
+
+    pub fn assert_matches_golden_with(
+        &self,
+        path: impl AsRef<Path>,
+        options: GoldenOptions,
+    ) -> Result<()> {
+        let path = path.as_ref();
+
+        if std::env::var("BOTTEST_UPDATE_GOLDEN").is_ok_and(|v| v == "1" || v == "true") {
+            return self.save_transcript(path);
+        }
+
+        let golden = Self::load_transcript(path)?;
+        let expected = golden.bot_replies();
+        let actual = self.bot_replies();
+
+        let mut diff = Vec::new();
+        for index in 0..expected.len().max(actual.len()) {
+            match (expected.get(index), actual.get(index)) {
+                (Some(e), Some(a)) if options.normalize(e) == options.normalize(a) => {}
+                (Some(e), Some(a)) => {
+                    diff.push(format!("  reply {}:\n    - {e}\n    + {a}", index + 1));
+                }
+                (Some(e), None) => diff.push(format!("  reply {}:\n    - {e}", index + 1)),
+                (None, Some(a)) => diff.push(format!("  reply {}:\n    + {a}", index + 1)),
+                (None, None) => {}
+            }
+        }
+
+        if diff.is_empty() {
+            return Ok(());
+        }
+
+        anyhow::bail!(
+            "Conversation does not match golden transcript {} ({} expected, {} actual replies):\n{}\nSet BOTTEST_UPDATE_GOLDEN=1 to accept the new transcript.",
+            path.display(),
+            expected.len(),
+            actual.len(),
+            diff.join("\n")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::RecordedMessage;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn record(replies: &[&str]) -> ConversationRecord {
+        let messages = replies
+            .iter()
+            .flat_map(|reply| {
+                [
+                    (MessageDirection::Incoming, "next"),
+                    (MessageDirection::Outgoing, *reply),
+                ]
+            })
+            .map(|(direction, content)| RecordedMessage {
+                timestamp: Utc::now(),
+                direction,
+                content: content.to_string(),
+                latency_ms: None,
+                quick_replies: Vec::new(),
+                transferred: false,
+            })
+            .collect();
+
+        ConversationRecord {
+            id: Uuid::new_v4(),
+            bot_name: "support".to_string(),
+            started_at: Utc::now(),
+            ended_at: None,
+            messages,
+            assertions: Vec::new(),
+            passed: true,
+        }
+    }
+
+    fn golden_path() -> std::path::PathBuf {
+        std::env::temp_dir()
+            .join(format!("bottest-golden-{}", Uuid::new_v4()))
+            .join("support.golden.json")
+    }
+
+    #[test]
+    fn test_golden_matches_identical_replies() {
+        let path = golden_path();
+        record(&["Hello!", "Your order shipped."])
+            .save_transcript(&path)
+            .unwrap();
+
+        let result = record(&["Hello!", "Your order shipped."]).assert_matches_golden(&path);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_golden_reports_changed_reply() {
+        let path = golden_path();
+        record(&["Hello!", "Your order shipped.", "Bye"])
+            .save_transcript(&path)
+            .unwrap();
+
+        let changed = record(&["Hello!", "Your order is delayed."]);
+        let err = changed
+            .assert_matches_golden(&path)
+            .unwrap_err()
+            .to_string();
+
+        let spaced = record(&["Hello!", "Your  order\nshipped.", "Bye"]);
+        let strict = spaced.assert_matches_golden(&path);
+        let relaxed = spaced
+            .assert_matches_golden_with(&path, GoldenOptions::default().normalize_whitespace());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+
+        assert!(err.contains("3 expected, 2 actual replies"));
+        assert!(err.contains("reply 2:\n    - Your order shipped.\n    + Your order is delayed."));
+        assert!(err.contains("reply 3:\n    - Bye"));
+        assert!(!err.contains("reply 1:"));
+
+        assert!(strict.is_err());
+        assert!(relaxed.is_ok());
+    }
+}
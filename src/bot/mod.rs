@@ -1,10 +1,21 @@
 
+mod assertions;
+mod chat;
 mod conversation;
+mod golden;
+mod replay;
 mod runner;
 
+pub use assertions::{ConversationAssertions, ReplyAssertion};
+pub use chat::{ChatReply, ConversationRunner};
+pub use golden::GoldenOptions;
+pub use replay::{replay, replay_against};
+
 use crate::fixtures::MessageDirection;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 use std::time::Duration;
 use uuid::Uuid;
 
@@ -94,12 +105,86 @@ pub struct ConversationRecord {
     pub passed: bool,
 }
 
+impl ConversationRecord {
+    pub fn save_transcript(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write transcript {}", path.display()))
+    }
+
+    pub fn load_transcript(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read transcript {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Invalid transcript {}", path.display()))
+    }
+
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("# Conversation with {}\n\n", self.bot_name);
+        md.push_str(&format!("- ID: `{}`\n", self.id));
+        md.push_str(&format!("- Started: {}\n", self.started_at.to_rfc3339()));
+        if let Some(ended_at) = self.ended_at {
+            md.push_str(&format!("- Ended: {}\n", ended_at.to_rfc3339()));
+        }
+        md.push_str(&format!(
+            "- Result: {}\n\n## Transcript\n\n",
+            if self.passed { "passed" } else { "failed" }
+        ));
+
+        for message in &self.messages {
+            let speaker = match message.direction {
+                MessageDirection::Incoming => "User",
+                MessageDirection::Outgoing => "Bot",
+            };
+            md.push_str(&format!("**{speaker}:** {}", message.content));
+            if let Some(latency) = message.latency_ms {
+                md.push_str(&format!(" _({latency} ms)_"));
+            }
+            md.push('\n');
+            if !message.quick_replies.is_empty() {
+                md.push_str(&format!(
+                    "  - Quick replies: {}\n",
+                    message.quick_replies.join(" | ")
+                ));
+            }
+            if message.transferred {
+                md.push_str("  - Transferred to a human agent\n");
+            }
+            md.push('\n');
+        }
+
+        if !self.assertions.is_empty() {
+            md.push_str("## Assertions\n\n");
+            for assertion in &self.assertions {
+                let mark = if assertion.passed { "x" } else { " " };
+                md.push_str(&format!(
+                    "- [{mark}] `{}` {}\n",
+                    assertion.assertion_type, assertion.message
+                ));
+            }
+        }
+
+        md
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordedMessage {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub direction: MessageDirection,
     pub content: String,
     pub latency_ms: Option<u64>,
+    #[serde(default)]
+    pub quick_replies: Vec<String>,
+    #[serde(default)]
+    pub transferred: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +195,28 @@ pub struct AssertionRecord {
     pub message: String,
 }
 
+impl AssertionRecord {
+    #[must_use]
+    pub fn from_result(assertion_type: &str, result: &AssertionResult) -> Self {
+        let message = match (&result.expected, &result.actual) {
+            (Some(expected), Some(actual)) if !result.passed => {
+                format!(
+                    "{} (expected: {expected}, actual: {actual})",
+                    result.message
+                )
+            }
+            _ => result.message.clone(),
+        };
+
+        Self {
+            timestamp: chrono::Utc::now(),
+            assertion_type: assertion_type.to_string(),
+            passed: result.passed,
+            message,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[derive(Default)]
 pub enum ConversationState {
@@ -156,6 +263,80 @@ mod tests {
         assert_eq!(state, ConversationState::Initial);
     }
 
+    fn sample_record() -> ConversationRecord {
+        let message = |direction, content: &str, latency_ms| RecordedMessage {
+            timestamp: chrono::Utc::now(),
+            direction,
+            content: content.to_string(),
+            latency_ms,
+            quick_replies: Vec::new(),
+            transferred: false,
+        };
+
+        ConversationRecord {
+            id: Uuid::new_v4(),
+            bot_name: "support".to_string(),
+            started_at: chrono::Utc::now(),
+            ended_at: Some(chrono::Utc::now()),
+            messages: vec![
+                message(MessageDirection::Incoming, "Hi there", None),
+                message(
+                    MessageDirection::Outgoing,
+                    "Hello! How can I help?",
+                    Some(85),
+                ),
+                message(MessageDirection::Incoming, "Where is my order?", None),
+                message(
+                    MessageDirection::Outgoing,
+                    "Let me check that for you",
+                    Some(120),
+                ),
+            ],
+            assertions: vec![AssertionRecord::from_result(
+                "contains",
+                &AssertionResult::pass("Reply contains 'check'"),
+            )],
+            passed: true,
+        }
+    }
+
+    #[test]
+    fn test_transcript_round_trip() {
+        let record = sample_record();
+        let path = std::env::temp_dir()
+            .join(format!("bottest-transcript-{}", record.id))
+            .join("support.json");
+
+        record.save_transcript(&path).unwrap();
+        let loaded = ConversationRecord::load_transcript(&path).unwrap();
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(loaded.id, record.id);
+        assert_eq!(loaded.bot_name, "support");
+        assert_eq!(loaded.messages.len(), 4);
+        assert_eq!(loaded.messages[1].latency_ms, Some(85));
+        assert_eq!(loaded.assertions.len(), 1);
+    }
+
+    #[test]
+    fn test_transcript_markdown_keeps_turn_order() {
+        let md = sample_record().to_markdown();
+
+        let turns = [
+            "**User:** Hi there",
+            "**Bot:** Hello! How can I help? _(85 ms)_",
+            "**User:** Where is my order?",
+            "**Bot:** Let me check that for you _(120 ms)_",
+        ];
+        let positions: Vec<usize> = turns
+            .iter()
+            .map(|turn| md.find(turn).unwrap_or_else(|| panic!("missing {turn}")))
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+        assert!(md.starts_with("# Conversation with support"));
+        assert!(md.contains("- [x] `contains` Reply contains 'check'"));
+    }
+
     #[test]
     fn test_bot_response_serialization() {
         let response = BotResponse {
@@ -0,0 +1,144 @@
+use super::{ConversationRecord, ConversationRunner};
+use crate::fixtures::MessageDirection;
+use crate::harness::TestContext;
+use crate::mocks::MockLLM;
+use anyhow::Result;
+
+pub async fn replay(record: &ConversationRecord, ctx: &TestContext) -> Result<ConversationRecord> {
+    let mut server = ctx.start_botserver().await?;
+    let result = replay_against(record, &server.url, ctx.mock_llm()).await;
+    server.stop().await?;
+    result
+}
+
+pub async fn replay_against(
+    record: &ConversationRecord,
+    base_url: &str,
+    llm: Option<&MockLLM>,
+) -> Result<ConversationRecord> {
+    let turns = recorded_turns(record);
+
+    if let Some(llm) = llm {
+        for (message, reply) in &turns {
+            if let Some(reply) = reply {
+                llm.expect_completion(message, reply).await;
+            }
+        }
+    }
+
+    let mut runner = ConversationRunner::new(base_url, &record.bot_name);
+    for (message, reply) in &turns {
+        runner.send(message).await?;
+        if let Some(reply) = reply {
+            runner.expect_reply_equals(reply);
+        }
+    }
+
+    Ok(runner.finish())
+}
+
+fn recorded_turns(record: &ConversationRecord) -> Vec<(String, Option<String>)> {
+    let mut turns: Vec<(String, Option<String>)> = Vec::new();
+    for message in &record.messages {
+        match message.direction {
+            MessageDirection::Incoming => turns.push((message.content.clone(), None)),
+            MessageDirection::Outgoing => {
+                if let Some((_, reply @ None)) = turns.last_mut() {
+                    *reply = Some(message.content.clone());
+                }
+            }
+        }
+    }
+    turns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::RecordedMessage;
+    use crate::ports::PortAllocator;
+    use uuid::Uuid;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn record(turns: &[(&str, &str)]) -> ConversationRecord {
+        let message = |direction, content: &str| RecordedMessage {
+            timestamp: chrono::Utc::now(),
+            direction,
+            content: content.to_string(),
+            latency_ms: None,
+            quick_replies: Vec::new(),
+            transferred: false,
+        };
+
+        ConversationRecord {
+            id: Uuid::new_v4(),
+            bot_name: "support".to_string(),
+            started_at: chrono::Utc::now(),
+            ended_at: Some(chrono::Utc::now()),
+            messages: turns
+                .iter()
+                .flat_map(|(incoming, outgoing)| {
+                    [
+                        message(MessageDirection::Incoming, incoming),
+                        message(MessageDirection::Outgoing, outgoing),
+                    ]
+                })
+                .collect(),
+            assertions: Vec::new(),
+            passed: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_reasserts_recorded_replies() {
+        let server = MockServer::start().await;
+        for (message, reply) in [
+            ("Hi there", "Hello! How can I help?"),
+            ("Where is my order?", "Let me check that for you"),
+        ] {
+            Mock::given(method("POST"))
+                .and(path("/api/bots/support/chat"))
+                .and(body_partial_json(serde_json::json!({"message": message})))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(serde_json::json!({"content": reply})),
+                )
+                .mount(&server)
+                .await;
+        }
+        let llm = MockLLM::start(PortAllocator::allocate()).await.unwrap();
+
+        let original = record(&[
+            ("Hi there", "Hello! How can I help?"),
+            ("Where is my order?", "Let me check that for you"),
+        ]);
+        let replayed = replay_against(&original, &server.uri(), Some(&llm))
+            .await
+            .unwrap();
+        assert!(replayed.passed);
+        assert_eq!(replayed.assertions.len(), 2);
+        assert_eq!(replayed.messages.len(), 4);
+
+        let completion: serde_json::Value = reqwest::Client::new()
+            .post(format!("{}/v1/chat/completions", llm.url()))
+            .json(&serde_json::json!({
+                "model": "gpt-4",
+                "messages": [{"role": "user", "content": "Where is my order?"}]
+            }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(
+            completion["choices"][0]["message"]["content"],
+            "Let me check that for you"
+        );
+
+        let drifted = record(&[("Hi there", "Welcome back!")]);
+        let replayed = replay_against(&drifted, &server.uri(), None).await.unwrap();
+        assert!(!replayed.passed);
+        assert!(replayed.assertions[0].message.contains("Welcome back!"));
+    }
+}
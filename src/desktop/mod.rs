@@ -1,8 +1,9 @@
-use anyhow::Result;
+use crate::web::Key;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone)]
 pub struct DesktopConfig {
@@ -87,6 +88,7 @@ pub struct DesktopApp {
     platform: Platform,
     process: Option<std::process::Child>,
     pid: Option<u32>,
+    owned: bool,
 }
 
 impl DesktopApp {
@@ -97,6 +99,7 @@ impl DesktopApp {
             platform: Platform::current(),
             process: None,
             pid: None,
+            owned: false,
         }
     }
 
@@ -117,13 +120,40 @@ impl DesktopApp {
         let child = cmd.spawn()?;
         self.pid = Some(child.id());
         self.process = Some(child);
+        self.owned = true;
 
         tokio::time::sleep(Duration::from_millis(500)).await;
 
         Ok(())
     }
 
+    pub fn attach(&mut self, pid: u32) -> Result<()> {
+        if !Self::process_alive(pid) {
+            anyhow::bail!("No running process with pid {pid} to attach to");
+        }
+        self.process = None;
+        self.pid = Some(pid);
+        self.owned = false;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn process_alive(pid: u32) -> bool {
+        use nix::sys::signal::kill;
+        use nix::unistd::Pid;
+        i32::try_from(pid).is_ok_and(|pid| kill(Pid::from_raw(pid), None).is_ok())
+    }
+
+    #[cfg(not(unix))]
+    fn process_alive(_pid: u32) -> bool {
+        false
+    }
+
     pub async fn close(&mut self) -> Result<()> {
+        if !self.owned {
+            self.pid = None;
+            return Ok(());
+        }
         if let Some(ref mut process) = self.process {
             #[cfg(unix)]
             {
@@ -145,6 +175,9 @@ impl DesktopApp {
     }
 
     pub fn is_running(&mut self) -> bool {
+        if !self.owned {
+            return self.pid.is_some_and(Self::process_alive);
+        }
         if let Some(ref mut process) = self.process {
             match process.try_wait() {
                 Ok(Some(_)) => {
@@ -165,6 +198,11 @@ impl DesktopApp {
         self.pid
     }
 
+    #[must_use]
+    pub const fn is_owned(&self) -> bool {
+        self.owned
+    }
+
     #[must_use]
     pub const fn platform(&self) -> Platform {
         self.platform
@@ -208,6 +246,85 @@ impl DesktopApp {
         anyhow::bail!("Linux desktop testing not available on this platform")
     }
 
+    pub fn send_keys(&self, text: &str) -> Result<()> {
+        match self.platform {
+            Platform::Linux => Self::xdotool(&["type", "--delay", "20", "--", text]),
+            Platform::Windows | Platform::MacOS => {
+                anyhow::bail!(
+                    "Desktop key input not yet implemented for {:?}",
+                    self.platform
+                )
+            }
+        }
+    }
+
+    pub fn send_hotkey(&self, keys: &[Key]) -> Result<()> {
+        if keys.is_empty() {
+            anyhow::bail!("Hotkey needs at least one key");
+        }
+        match self.platform {
+            Platform::Linux => {
+                let combo = keys
+                    .iter()
+                    .map(|key| Self::x11_key_name(*key))
+                    .collect::<Vec<_>>()
+                    .join("+");
+                Self::xdotool(&["key", "--clearmodifiers", &combo])
+            }
+            Platform::Windows | Platform::MacOS => {
+                anyhow::bail!(
+                    "Desktop key input not yet implemented for {:?}",
+                    self.platform
+                )
+            }
+        }
+    }
+
+    fn xdotool(args: &[&str]) -> Result<()> {
+        let status = std::process::Command::new("xdotool")
+            .args(args)
+            .status()
+            .context("Failed to run xdotool (is it installed?)")?;
+        if !status.success() {
+            anyhow::bail!("xdotool {} exited with {status}", args.join(" "));
+        }
+        Ok(())
+    }
+
+    const fn x11_key_name(key: Key) -> &'static str {
+        match key {
+            Key::Enter => "Return",
+            Key::Tab => "Tab",
+            Key::Escape => "Escape",
+            Key::Backspace => "BackSpace",
+            Key::Delete => "Delete",
+            Key::ArrowUp => "Up",
+            Key::ArrowDown => "Down",
+            Key::ArrowLeft => "Left",
+            Key::ArrowRight => "Right",
+            Key::Home => "Home",
+            Key::End => "End",
+            Key::PageUp => "Prior",
+            Key::PageDown => "Next",
+            Key::F1 => "F1",
+            Key::F2 => "F2",
+            Key::F3 => "F3",
+            Key::F4 => "F4",
+            Key::F5 => "F5",
+            Key::F6 => "F6",
+            Key::F7 => "F7",
+            Key::F8 => "F8",
+            Key::F9 => "F9",
+            Key::F10 => "F10",
+            Key::F11 => "F11",
+            Key::F12 => "F12",
+            Key::Shift => "shift",
+            Key::Control => "ctrl",
+            Key::Alt => "alt",
+            Key::Meta => "super",
+        }
+    }
+
     pub fn screenshot(&self) -> Result<Screenshot> {
         let _ = &self.platform;
         anyhow::bail!("Screenshot functionality not yet implemented")
@@ -216,6 +333,9 @@ impl DesktopApp {
 
 impl Drop for DesktopApp {
     fn drop(&mut self) {
+        if !self.owned {
+            return;
+        }
         if let Some(ref mut process) = self.process {
             let _ = process.kill();
             let _ = process.wait();
@@ -362,6 +482,83 @@ pub struct TestStep {
     pub error: Option<String>,
 }
 
+pub struct DesktopTest<'a> {
+    name: String,
+    app: &'a mut DesktopApp,
+    steps: Vec<TestStep>,
+    screenshots: Vec<PathBuf>,
+}
+
+impl<'a> DesktopTest<'a> {
+    pub fn run<F>(name: &str, app: &'a mut DesktopApp, body: F) -> DesktopTestResult
+    where
+        F: FnOnce(&mut DesktopTest<'a>) -> Result<()>,
+    {
+        let start = Instant::now();
+        let mut test = Self {
+            name: name.to_string(),
+            app,
+            steps: Vec::new(),
+            screenshots: Vec::new(),
+        };
+        let outcome = body(&mut test);
+
+        DesktopTestResult {
+            name: test.name,
+            passed: outcome.is_ok() && test.steps.iter().all(|step| step.passed),
+            duration_ms: start.elapsed().as_millis() as u64,
+            steps: test.steps,
+            screenshots: test.screenshots,
+            error: outcome.err().map(|e| format!("{e:#}")),
+        }
+    }
+
+    pub fn step<T, F>(&mut self, name: &str, action: F) -> Result<T>
+    where
+        F: FnOnce(&mut DesktopApp) -> Result<T>,
+    {
+        let start = Instant::now();
+        let outcome = action(&mut *self.app);
+        let duration_ms = start.elapsed().as_millis() as u64;
+
+        let error = outcome.as_ref().err().map(|e| format!("{e:#}"));
+        if error.is_some() {
+            self.capture_failure(name);
+        }
+        self.steps.push(TestStep {
+            name: name.to_string(),
+            passed: error.is_none(),
+            duration_ms,
+            error,
+        });
+
+        outcome.with_context(|| format!("Step '{name}' failed"))
+    }
+
+    #[must_use]
+    pub fn app(&self) -> &DesktopApp {
+        &*self.app
+    }
+
+    fn capture_failure(&mut self, step: &str) {
+        if !self.app.config.screenshot_on_failure {
+            return;
+        }
+        let Ok(screenshot) = self.app.screenshot() else {
+            return;
+        };
+        let slug = |s: &str| s.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+        let path =
+            self.app
+                .config
+                .screenshot_dir
+                .join(format!("{}-{}.png", slug(&self.name), slug(step)));
+        if screenshot.save(&path).is_ok() {
+            self.screenshots.push(path);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -395,6 +592,67 @@ mod tests {
         ));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_attach_does_not_own_process() {
+        let mut sleeper = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+
+        let mut app = DesktopApp::new(DesktopConfig::default());
+        app.attach(sleeper.id()).unwrap();
+        assert_eq!(app.pid(), Some(sleeper.id()));
+        assert!(!app.is_owned());
+        assert!(app.is_running());
+        drop(app);
+
+        assert!(sleeper.try_wait().unwrap().is_none());
+        let mut app = DesktopApp::new(DesktopConfig::default());
+        app.attach(sleeper.id()).unwrap();
+        sleeper.kill().unwrap();
+        sleeper.wait().unwrap();
+        assert!(!app.is_running());
+
+        let mut missing = DesktopApp::new(DesktopConfig::default());
+        assert!(missing.attach(sleeper.id()).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_send_keys_types_into_focused_field() {
+        if std::env::var_os("DISPLAY").is_none() {
+            eprintln!("Skipping: DISPLAY is not set");
+            return;
+        }
+        let Ok(mut dialog) = std::process::Command::new("zenity")
+            .args(["--entry", "--title", "bottest-keys"])
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+        else {
+            eprintln!("Skipping: zenity is not available");
+            return;
+        };
+        std::thread::sleep(Duration::from_secs(1));
+
+        let mut app = DesktopApp::new(DesktopConfig::default());
+        app.attach(dialog.id()).unwrap();
+        if let Err(e) = app.send_keys("hello desktop") {
+            let _ = dialog.kill();
+            let _ = dialog.wait();
+            eprintln!("Skipping: {e}");
+            return;
+        }
+        app.send_hotkey(&[Key::Enter]).unwrap();
+
+        let output = dialog.wait_with_output().unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            "hello desktop"
+        );
+        assert!(app.send_hotkey(&[]).is_err());
+    }
+
     #[test]
     fn test_element_locator() {
         let by_id = ElementLocator::accessibility_id("submit-button");
@@ -419,6 +677,35 @@ mod tests {
         assert_eq!(bounds.width, 800);
     }
 
+    #[test]
+    fn test_desktop_test_records_steps_until_failure() {
+        let mut app = DesktopApp::new(DesktopConfig::default());
+        let result = DesktopTest::run("save document", &mut app, |test| {
+            test.step("open editor", |_| Ok(()))?;
+            let pid = test.step("read pid", |app| Ok(app.pid()))?;
+            assert_eq!(pid, None);
+            test.step("press save", |_| -> Result<()> {
+                anyhow::bail!("Save button not found")
+            })?;
+            test.step("never runs", |_| Ok(()))
+        });
+
+        assert_eq!(result.name, "save document");
+        assert!(!result.passed);
+        let names: Vec<&str> = result.steps.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["open editor", "read pid", "press save"]);
+        assert!(result.steps[0].passed && result.steps[1].passed);
+        assert_eq!(
+            result.steps[2].error.as_deref(),
+            Some("Save button not found")
+        );
+        assert!(result
+            .error
+            .as_deref()
+            .is_some_and(|e| e.contains("Step 'press save' failed")));
+        assert!(result.screenshots.is_empty());
+    }
+
     #[test]
     fn test_desktop_test_result() {
         let result = DesktopTestResult {
@@ -0,0 +1,152 @@
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum HarnessError {
+    BinaryNotFound {
+        service: &'static str,
+        details: String,
+    },
+    PortInUse {
+        service: &'static str,
+        port: u16,
+    },
+    HealthTimeout {
+        service: &'static str,
+        timeout: Duration,
+    },
+    MigrationFailed(String),
+    Io(std::io::Error),
+    Other(anyhow::Error),
+}
+
+impl HarnessError {
+    #[must_use]
+    pub fn binary_not_found(service: &'static str, error: &anyhow::Error) -> Self {
+        Self::BinaryNotFound {
+            service,
+            details: format!("{error:#}"),
+        }
+    }
+
+    #[must_use]
+    pub const fn is_unavailable(&self) -> bool {
+        matches!(self, Self::BinaryNotFound { .. })
+    }
+
+    #[must_use]
+    pub const fn service(&self) -> Option<&'static str> {
+        match self {
+            Self::BinaryNotFound { service, .. }
+            | Self::PortInUse { service, .. }
+            | Self::HealthTimeout { service, .. } => Some(*service),
+            Self::MigrationFailed(_) | Self::Io(_) | Self::Other(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for HarnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BinaryNotFound { service, details } => {
+                write!(f, "{service} binary not found: {details}")
+            }
+            Self::PortInUse { service, port } => {
+                write!(f, "{service} port {port} is already in use")
+            }
+            Self::HealthTimeout { service, timeout } => write!(
+                f,
+                "{service} did not become healthy within {}s",
+                timeout.as_secs()
+            ),
+            Self::MigrationFailed(details) => write!(f, "Database migrations failed: {details}"),
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+            Self::Other(e) => write!(f, "{e:#}"),
+        }
+    }
+}
+
+impl std::error::Error for HarnessError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Other(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for HarnessError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<anyhow::Error> for HarnessError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::Other(error)
+    }
+}
+
+pub(crate) fn ensure_port_free(service: &'static str, port: u16) -> Result<(), HarnessError> {
+    match std::net::TcpListener::bind(("127.0.0.1", port)) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => {
+            Err(HarnessError::PortInUse { service, port })
+        }
+        Err(e) => Err(HarnessError::Io(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_port_in_use_maps_to_variant() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let err = ensure_port_free("Redis", port).unwrap_err();
+        assert!(matches!(
+            err,
+            HarnessError::PortInUse {
+                service: "Redis",
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            format!("Redis port {port} is already in use")
+        );
+        drop(listener);
+        assert!(ensure_port_free("Redis", port).is_ok());
+    }
+
+    #[test]
+    fn test_error_variants_classify_and_convert() {
+        let missing =
+            HarnessError::binary_not_found("PostgreSQL", &anyhow::anyhow!("initdb not on PATH"));
+        assert!(missing.is_unavailable());
+        assert_eq!(missing.service(), Some("PostgreSQL"));
+        assert!(missing.to_string().contains("initdb not on PATH"));
+
+        let timeout = HarnessError::HealthTimeout {
+            service: "MinIO",
+            timeout: Duration::from_secs(30),
+        };
+        assert!(!timeout.is_unavailable());
+        assert_eq!(
+            timeout.to_string(),
+            "MinIO did not become healthy within 30s"
+        );
+
+        let io: HarnessError = std::io::Error::from(std::io::ErrorKind::PermissionDenied).into();
+        assert!(matches!(io, HarnessError::Io(_)));
+
+        let other: HarnessError = anyhow::anyhow!("boom").into();
+        assert!(matches!(other, HarnessError::Other(_)));
+        let wrapped: anyhow::Error = HarnessError::MigrationFailed("bad sql".into()).into();
+        assert!(wrapped.to_string().contains("bad sql"));
+    }
+}
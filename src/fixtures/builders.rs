@@ -0,0 +1,425 @@
+use super::{Bot, Channel, Customer, Role, Session, SessionState, User};
+use crate::mocks::Clock;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+impl Bot {
+    #[must_use]
+    pub fn builder() -> BotBuilder {
+        BotBuilder {
+            bot: Self::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn builder_with_clock(clock: &Clock) -> BotBuilder {
+        BotBuilder {
+            bot: Self::with_clock(clock),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BotBuilder {
+    bot: Bot,
+}
+
+impl BotBuilder {
+    #[must_use]
+    pub const fn id(mut self, id: Uuid) -> Self {
+        self.bot.id = id;
+        self
+    }
+
+    #[must_use]
+    pub fn name(mut self, name: &str) -> Self {
+        self.bot.name = name.to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn description(mut self, description: &str) -> Self {
+        self.bot.description = Some(description.to_string());
+        self
+    }
+
+    #[must_use]
+    pub const fn kb(mut self, enabled: bool) -> Self {
+        self.bot.kb_enabled = enabled;
+        self
+    }
+
+    #[must_use]
+    pub fn llm(mut self, enabled: bool) -> Self {
+        self.bot.llm_enabled = enabled;
+        if !enabled {
+            self.bot.llm_model = None;
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn llm_model(mut self, model: &str) -> Self {
+        self.bot.llm_enabled = true;
+        self.bot.llm_model = Some(model.to_string());
+        self
+    }
+
+    #[must_use]
+    pub const fn active(mut self, active: bool) -> Self {
+        self.bot.active = active;
+        self
+    }
+
+    #[must_use]
+    pub fn config(mut self, key: &str, value: serde_json::Value) -> Self {
+        self.bot.config.insert(key.to_string(), value);
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> Bot {
+        self.bot
+    }
+}
+
+impl Customer {
+    #[must_use]
+    pub fn builder() -> CustomerBuilder {
+        CustomerBuilder {
+            customer: Self::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn builder_with_clock(clock: &Clock) -> CustomerBuilder {
+        CustomerBuilder {
+            customer: Self::with_clock(clock),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomerBuilder {
+    customer: Customer,
+}
+
+impl CustomerBuilder {
+    #[must_use]
+    pub const fn id(mut self, id: Uuid) -> Self {
+        self.customer.id = id;
+        self
+    }
+
+    #[must_use]
+    pub fn external_id(mut self, external_id: &str) -> Self {
+        self.customer.external_id = external_id.to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn phone(mut self, phone: &str) -> Self {
+        self.customer.phone = Some(phone.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn email(mut self, email: &str) -> Self {
+        self.customer.email = Some(email.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn name(mut self, name: &str) -> Self {
+        self.customer.name = Some(name.to_string());
+        self
+    }
+
+    #[must_use]
+    pub const fn channel(mut self, channel: Channel) -> Self {
+        self.customer.channel = channel;
+        self
+    }
+
+    #[must_use]
+    pub fn metadata(mut self, key: &str, value: &str) -> Self {
+        self.customer
+            .metadata
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> Customer {
+        self.customer
+    }
+}
+
+impl Session {
+    #[must_use]
+    pub fn builder() -> SessionBuilder {
+        SessionBuilder {
+            session: Self::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn builder_with_clock(clock: &Clock) -> SessionBuilder {
+        SessionBuilder {
+            session: Self::with_clock(clock),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionBuilder {
+    session: Session,
+}
+
+impl SessionBuilder {
+    #[must_use]
+    pub const fn id(mut self, id: Uuid) -> Self {
+        self.session.id = id;
+        self
+    }
+
+    #[must_use]
+    pub const fn bot(mut self, bot: &Bot) -> Self {
+        self.session.bot_id = bot.id;
+        self
+    }
+
+    #[must_use]
+    pub const fn customer(mut self, customer: &Customer) -> Self {
+        self.session.customer_id = customer.id;
+        self.session.channel = customer.channel;
+        self
+    }
+
+    #[must_use]
+    pub const fn channel(mut self, channel: Channel) -> Self {
+        self.session.channel = channel;
+        self
+    }
+
+    #[must_use]
+    pub const fn state(mut self, state: SessionState) -> Self {
+        self.session.state = state;
+        self
+    }
+
+    #[must_use]
+    pub fn context(mut self, key: &str, value: serde_json::Value) -> Self {
+        self.session.context.insert(key.to_string(), value);
+        self
+    }
+
+    #[must_use]
+    pub const fn ended_at(mut self, ended_at: DateTime<Utc>) -> Self {
+        self.session.state = SessionState::Ended;
+        self.session.ended_at = Some(ended_at);
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> Session {
+        self.session
+    }
+}
+
+impl User {
+    #[must_use]
+    pub fn builder() -> UserBuilder {
+        UserBuilder {
+            user: Self::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn builder_with_clock(clock: &Clock) -> UserBuilder {
+        UserBuilder {
+            user: Self::with_clock(clock),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserBuilder {
+    user: User,
+}
+
+impl UserBuilder {
+    #[must_use]
+    pub const fn id(mut self, id: Uuid) -> Self {
+        self.user.id = id;
+        self
+    }
+
+    #[must_use]
+    pub fn email(mut self, email: &str) -> Self {
+        self.user.email = email.to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn name(mut self, name: &str) -> Self {
+        self.user.name = name.to_string();
+        self
+    }
+
+    #[must_use]
+    pub const fn role(mut self, role: Role) -> Self {
+        self.user.role = role;
+        self
+    }
+
+    #[must_use]
+    pub fn metadata(mut self, key: &str, value: &str) -> Self {
+        self.user
+            .metadata
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> User {
+        self.user
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bot_builder_defaults() {
+        let bot = Bot::builder().build();
+        let default = Bot::default();
+
+        assert_eq!(bot.name, default.name);
+        assert_eq!(bot.description, default.description);
+        assert_eq!(bot.kb_enabled, default.kb_enabled);
+        assert_eq!(bot.llm_enabled, default.llm_enabled);
+        assert_eq!(bot.llm_model, default.llm_model);
+        assert!(bot.active);
+        assert!(bot.config.is_empty());
+    }
+
+    #[test]
+    fn test_bot_builder_setters() {
+        let id = Uuid::new_v4();
+        let bot = Bot::builder()
+            .id(id)
+            .name("sales")
+            .description("Sales assistant")
+            .kb(true)
+            .llm_model("gpt-4o")
+            .active(false)
+            .config("temperature", json!(0.2))
+            .config("tools", json!(["search"]))
+            .build();
+
+        assert_eq!(bot.id, id);
+        assert_eq!(bot.name, "sales");
+        assert_eq!(bot.description.as_deref(), Some("Sales assistant"));
+        assert!(bot.kb_enabled);
+        assert!(bot.llm_enabled);
+        assert_eq!(bot.llm_model.as_deref(), Some("gpt-4o"));
+        assert!(!bot.active);
+        assert_eq!(bot.config["temperature"], json!(0.2));
+        assert_eq!(bot.config["tools"], json!(["search"]));
+
+        let rules = Bot::builder().llm(false).build();
+        assert!(!rules.llm_enabled);
+        assert!(rules.llm_model.is_none());
+    }
+
+    #[test]
+    fn test_customer_builder() {
+        let default = Customer::builder().build();
+        assert_eq!(default.channel, Channel::WhatsApp);
+        assert!(default.external_id.starts_with("ext_"));
+        assert!(default.email.is_none());
+
+        let id = Uuid::new_v4();
+        let customer = Customer::builder()
+            .id(id)
+            .external_id("web_42")
+            .phone("+15550001111")
+            .email("jane@example.com")
+            .name("Jane")
+            .channel(Channel::Web)
+            .metadata("plan", "pro")
+            .build();
+
+        assert_eq!(customer.id, id);
+        assert_eq!(customer.external_id, "web_42");
+        assert_eq!(customer.phone.as_deref(), Some("+15550001111"));
+        assert_eq!(customer.email.as_deref(), Some("jane@example.com"));
+        assert_eq!(customer.name.as_deref(), Some("Jane"));
+        assert_eq!(customer.channel, Channel::Web);
+        assert_eq!(customer.metadata["plan"], "pro");
+    }
+
+    #[test]
+    fn test_session_builder() {
+        let clock = Clock::seeded(7);
+        let default = Session::builder_with_clock(&clock).build();
+        assert_eq!(default.state, SessionState::Active);
+        assert!(default.ended_at.is_none());
+        assert_eq!(default.started_at, clock.now());
+
+        let bot = Bot::builder_with_clock(&clock).name("support").build();
+        let customer = Customer::builder_with_clock(&clock)
+            .channel(Channel::Teams)
+            .build();
+        let id = Uuid::new_v4();
+        clock.advance(std::time::Duration::from_secs(60));
+        let ended = clock.now();
+        let session = Session::builder_with_clock(&clock)
+            .id(id)
+            .bot(&bot)
+            .customer(&customer)
+            .context("topic", json!("billing"))
+            .ended_at(ended)
+            .build();
+
+        assert_eq!(session.id, id);
+        assert_eq!(session.bot_id, bot.id);
+        assert_eq!(session.customer_id, customer.id);
+        assert_eq!(session.channel, Channel::Teams);
+        assert_eq!(session.context["topic"], json!("billing"));
+        assert_eq!(session.state, SessionState::Ended);
+        assert_eq!(session.ended_at, Some(ended));
+
+        let waiting = Session::builder_with_clock(&clock)
+            .channel(Channel::Sms)
+            .state(SessionState::Waiting)
+            .build();
+        assert_eq!(waiting.channel, Channel::Sms);
+        assert_eq!(waiting.state, SessionState::Waiting);
+    }
+
+    #[test]
+    fn test_user_builder() {
+        let default = User::builder().build();
+        assert_eq!(default.role, Role::User);
+        assert_eq!(default.email, "user@example.com");
+
+        let id = Uuid::new_v4();
+        let user = User::builder()
+            .id(id)
+            .email("ops@test.com")
+            .name("Ops")
+            .role(Role::Attendant)
+            .metadata("team", "night")
+            .build();
+
+        assert_eq!(user.id, id);
+        assert_eq!(user.email, "ops@test.com");
+        assert_eq!(user.name, "Ops");
+        assert_eq!(user.role, Role::Attendant);
+        assert_eq!(user.metadata["team"], "night");
+    }
+}
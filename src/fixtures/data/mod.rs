@@ -48,8 +48,7 @@ pub fn sample_bot_config() -> Value {
     })
 }
 
-#[must_use]
-pub fn whatsapp_text_message(from: &str, text: &str) -> Value {
+fn whatsapp_incoming(from: &str, message_type: &str, content: Value) -> Value {
     json!({
         "object": "whatsapp_business_account",
         "entry": [{
@@ -71,10 +70,8 @@ pub fn whatsapp_text_message(from: &str, text: &str) -> Value {
                         "from": from,
                         "id": format!("wamid.{}", uuid::Uuid::new_v4().to_string().replace('-', "")),
                         "timestamp": chrono::Utc::now().timestamp().to_string(),
-                        "type": "text",
-                        "text": {
-                            "body": text
-                        }
+                        "type": message_type,
+                        message_type: content
                     }]
                 },
                 "field": "messages"
@@ -83,47 +80,99 @@ pub fn whatsapp_text_message(from: &str, text: &str) -> Value {
     })
 }
 
+#[must_use]
+pub fn whatsapp_text_message(from: &str, text: &str) -> Value {
+    whatsapp_incoming(from, "text", json!({ "body": text }))
+}
+
 #[must_use]
 pub fn whatsapp_button_reply(from: &str, button_id: &str, button_text: &str) -> Value {
-    json!({
-        "object": "whatsapp_business_account",
-        "entry": [{
-            "id": "123456789",
-            "changes": [{
-                "value": {
-                    "messaging_product": "whatsapp",
-                    "metadata": {
-                        "display_phone_number": "15551234567",
-                        "phone_number_id": "987654321"
-                    },
-                    "contacts": [{
-                        "profile": {
-                            "name": "Test User"
-                        },
-                        "wa_id": from
-                    }],
-                    "messages": [{
-                        "from": from,
-                        "id": format!("wamid.{}", uuid::Uuid::new_v4().to_string().replace('-', "")),
-                        "timestamp": chrono::Utc::now().timestamp().to_string(),
-                        "type": "interactive",
-                        "interactive": {
-                            "type": "button_reply",
-                            "button_reply": {
-                                "id": button_id,
-                                "title": button_text
-                            }
-                        }
-                    }]
-                },
-                "field": "messages"
-            }]
-        }]
-    })
+    whatsapp_incoming(
+        from,
+        "interactive",
+        json!({
+            "type": "button_reply",
+            "button_reply": {
+                "id": button_id,
+                "title": button_text
+            }
+        }),
+    )
 }
 
 #[must_use]
-pub fn teams_message_activity(from_id: &str, from_name: &str, text: &str) -> Value {
+pub fn whatsapp_audio_message(from: &str, media_id: &str) -> Value {
+    whatsapp_incoming(
+        from,
+        "audio",
+        json!({
+            "id": media_id,
+            "mime_type": "audio/ogg; codecs=opus",
+            "sha256": "abc123",
+            "voice": true
+        }),
+    )
+}
+
+#[must_use]
+pub fn whatsapp_video_message(from: &str, media_id: &str, caption: Option<&str>) -> Value {
+    whatsapp_incoming(
+        from,
+        "video",
+        json!({
+            "id": media_id,
+            "mime_type": "video/mp4",
+            "sha256": "abc123",
+            "caption": caption
+        }),
+    )
+}
+
+#[must_use]
+pub fn whatsapp_location_message(
+    from: &str,
+    latitude: f64,
+    longitude: f64,
+    name: &str,
+    address: &str,
+) -> Value {
+    whatsapp_incoming(
+        from,
+        "location",
+        json!({
+            "latitude": latitude,
+            "longitude": longitude,
+            "name": name,
+            "address": address
+        }),
+    )
+}
+
+#[must_use]
+pub fn whatsapp_contact_message(from: &str, contact_name: &str, contact_phone: &str) -> Value {
+    let wa_id: String = contact_phone.chars().filter(char::is_ascii_digit).collect();
+    let first_name = contact_name
+        .split_whitespace()
+        .next()
+        .unwrap_or(contact_name);
+    whatsapp_incoming(
+        from,
+        "contacts",
+        json!([{
+            "name": {
+                "formatted_name": contact_name,
+                "first_name": first_name
+            },
+            "phones": [{
+                "phone": contact_phone,
+                "type": "CELL",
+                "wa_id": wa_id
+            }]
+        }]),
+    )
+}
+
+fn teams_activity(from_id: &str, from_name: &str) -> Value {
     json!({
         "type": "message",
         "id": uuid::Uuid::new_v4().to_string(),
@@ -144,8 +193,6 @@ pub fn teams_message_activity(from_id: &str, from_name: &str, text: &str) -> Val
             "id": "28:test-bot-id",
             "name": "TestBot"
         },
-        "text": text,
-        "textFormat": "plain",
         "locale": "en-US",
         "channelData": {
             "tenant": {
@@ -155,6 +202,74 @@ pub fn teams_message_activity(from_id: &str, from_name: &str, text: &str) -> Val
     })
 }
 
+#[must_use]
+pub fn teams_message_activity(from_id: &str, from_name: &str, text: &str) -> Value {
+    let mut activity = teams_activity(from_id, from_name);
+    activity["text"] = json!(text);
+    activity["textFormat"] = json!("plain");
+    activity
+}
+
+#[must_use]
+pub fn teams_audio_message(from_id: &str, from_name: &str, content_url: &str) -> Value {
+    let mut activity = teams_activity(from_id, from_name);
+    activity["attachments"] = json!([{
+        "contentType": "audio/mpeg",
+        "contentUrl": content_url,
+        "name": "voice-message.mp3"
+    }]);
+    activity
+}
+
+#[must_use]
+pub fn teams_video_message(from_id: &str, from_name: &str, content_url: &str) -> Value {
+    let mut activity = teams_activity(from_id, from_name);
+    activity["attachments"] = json!([{
+        "contentType": "video/mp4",
+        "contentUrl": content_url,
+        "name": "video.mp4"
+    }]);
+    activity
+}
+
+#[must_use]
+pub fn teams_location_message(
+    from_id: &str,
+    from_name: &str,
+    latitude: f64,
+    longitude: f64,
+    name: &str,
+) -> Value {
+    let mut activity = teams_activity(from_id, from_name);
+    activity["text"] = json!(name);
+    activity["textFormat"] = json!("plain");
+    activity["entities"] = json!([{
+        "type": "GeoCoordinates",
+        "latitude": latitude,
+        "longitude": longitude,
+        "name": name
+    }]);
+    activity
+}
+
+#[must_use]
+pub fn teams_contact_message(
+    from_id: &str,
+    from_name: &str,
+    contact_name: &str,
+    contact_phone: &str,
+) -> Value {
+    let mut activity = teams_activity(from_id, from_name);
+    activity["attachments"] = json!([{
+        "contentType": "text/vcard",
+        "name": format!("{contact_name}.vcf"),
+        "content": format!(
+            "BEGIN:VCARD\nVERSION:3.0\nFN:{contact_name}\nTEL;TYPE=CELL:{contact_phone}\nEND:VCARD"
+        )
+    }]);
+    activity
+}
+
 #[must_use]
 pub fn openai_chat_request(messages: Vec<(&str, &str)>) -> Value {
     let msgs: Vec<Value> = messages
@@ -440,6 +555,63 @@ mod tests {
         assert_eq!(activity["channelId"], "msteams");
     }
 
+    #[test]
+    fn test_whatsapp_media_location_and_contact_messages() {
+        let audio = whatsapp_audio_message("15551234567", "media-audio-1");
+        let message = &audio["entry"][0]["changes"][0]["value"]["messages"][0];
+        assert_eq!(message["type"], "audio");
+        assert_eq!(message["audio"]["id"], "media-audio-1");
+        assert_eq!(message["audio"]["voice"], true);
+
+        let video = whatsapp_video_message("15551234567", "media-video-1", Some("Demo"));
+        let message = &video["entry"][0]["changes"][0]["value"]["messages"][0];
+        assert_eq!(message["type"], "video");
+        assert_eq!(message["video"]["mime_type"], "video/mp4");
+        assert_eq!(message["video"]["caption"], "Demo");
+
+        let location =
+            whatsapp_location_message("15551234567", -23.5505, -46.6333, "Office", "Av. Paulista");
+        let message = &location["entry"][0]["changes"][0]["value"]["messages"][0];
+        assert_eq!(message["type"], "location");
+        assert_eq!(message["location"]["latitude"], -23.5505);
+        assert_eq!(message["location"]["longitude"], -46.6333);
+        assert_eq!(message["location"]["name"], "Office");
+
+        let contact = whatsapp_contact_message("15551234567", "Jane Doe", "+1 555 000 1111");
+        let message = &contact["entry"][0]["changes"][0]["value"]["messages"][0];
+        assert_eq!(message["type"], "contacts");
+        assert_eq!(message["contacts"][0]["name"]["formatted_name"], "Jane Doe");
+        assert_eq!(message["contacts"][0]["name"]["first_name"], "Jane");
+        assert_eq!(message["contacts"][0]["phones"][0]["wa_id"], "15550001111");
+    }
+
+    #[test]
+    fn test_teams_media_location_and_contact_messages() {
+        let audio = teams_audio_message("user-1", "Test User", "https://example.com/a.mp3");
+        assert_eq!(audio["type"], "message");
+        assert_eq!(audio["attachments"][0]["contentType"], "audio/mpeg");
+        assert_eq!(
+            audio["attachments"][0]["contentUrl"],
+            "https://example.com/a.mp3"
+        );
+
+        let video = teams_video_message("user-1", "Test User", "https://example.com/v.mp4");
+        assert_eq!(video["attachments"][0]["contentType"], "video/mp4");
+
+        let location = teams_location_message("user-1", "Test User", 47.64, -122.13, "Redmond");
+        assert_eq!(location["type"], "message");
+        assert_eq!(location["entities"][0]["type"], "GeoCoordinates");
+        assert_eq!(location["entities"][0]["latitude"], 47.64);
+        assert_eq!(location["entities"][0]["name"], "Redmond");
+
+        let contact = teams_contact_message("user-1", "Test User", "Jane Doe", "+15550001111");
+        assert_eq!(contact["attachments"][0]["contentType"], "text/vcard");
+        assert!(contact["attachments"][0]["content"]
+            .as_str()
+            .unwrap()
+            .contains("TEL;TYPE=CELL:+15550001111"));
+    }
+
     #[test]
     fn test_openai_chat_response() {
         let response = openai_chat_response("Hello, how can I help?");
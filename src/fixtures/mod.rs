@@ -1,7 +1,18 @@
+mod builders;
 pub mod data;
+mod queue;
+mod scenario;
 pub mod scripts;
+mod seed;
 
+pub use builders::{BotBuilder, CustomerBuilder, SessionBuilder, UserBuilder};
+pub use queue::{QueueSimulator, MINUTES_PER_POSITION};
+pub use scenario::Scenario;
+pub use seed::{is_seeded, reset_seed, with_seed};
+
+use crate::mocks::Clock;
 use chrono::{DateTime, Utc};
+use seed::default_clock;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -17,20 +28,28 @@ pub struct User {
     pub metadata: HashMap<String, String>,
 }
 
-impl Default for User {
-    fn default() -> Self {
+impl User {
+    #[must_use]
+    pub fn with_clock(clock: &Clock) -> Self {
+        let now = clock.now();
         Self {
-            id: Uuid::new_v4(),
+            id: clock.next_id(),
             email: "user@example.com".to_string(),
             name: "Test User".to_string(),
             role: Role::User,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+            created_at: now,
+            updated_at: now,
             metadata: HashMap::new(),
         }
     }
 }
 
+impl Default for User {
+    fn default() -> Self {
+        Self::with_clock(&default_clock())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 #[derive(Default)]
@@ -42,7 +61,6 @@ pub enum Role {
     Guest,
 }
 
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Customer {
     pub id: Uuid,
@@ -56,22 +74,30 @@ pub struct Customer {
     pub metadata: HashMap<String, String>,
 }
 
-impl Default for Customer {
-    fn default() -> Self {
+impl Customer {
+    #[must_use]
+    pub fn with_clock(clock: &Clock) -> Self {
+        let now = clock.now();
         Self {
-            id: Uuid::new_v4(),
-            external_id: format!("ext_{}", Uuid::new_v4()),
+            id: clock.next_id(),
+            external_id: format!("ext_{}", clock.next_id()),
             phone: Some("+15551234567".to_string()),
             email: None,
             name: Some("Test Customer".to_string()),
             channel: Channel::WhatsApp,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+            created_at: now,
+            updated_at: now,
             metadata: HashMap::new(),
         }
     }
 }
 
+impl Default for Customer {
+    fn default() -> Self {
+        Self::with_clock(&default_clock())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 #[derive(Default)]
@@ -85,7 +111,6 @@ pub enum Channel {
     Api,
 }
 
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bot {
     pub id: Uuid,
@@ -100,23 +125,31 @@ pub struct Bot {
     pub config: HashMap<String, serde_json::Value>,
 }
 
-impl Default for Bot {
-    fn default() -> Self {
+impl Bot {
+    #[must_use]
+    pub fn with_clock(clock: &Clock) -> Self {
+        let now = clock.now();
         Self {
-            id: Uuid::new_v4(),
+            id: clock.next_id(),
             name: "test-bot".to_string(),
             description: Some("Test bot for automated testing".to_string()),
             kb_enabled: false,
             llm_enabled: true,
             llm_model: Some("gpt-4".to_string()),
             active: true,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+            created_at: now,
+            updated_at: now,
             config: HashMap::new(),
         }
     }
 }
 
+impl Default for Bot {
+    fn default() -> Self {
+        Self::with_clock(&default_clock())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
     pub id: Uuid,
@@ -130,22 +163,30 @@ pub struct Session {
     pub ended_at: Option<DateTime<Utc>>,
 }
 
-impl Default for Session {
-    fn default() -> Self {
+impl Session {
+    #[must_use]
+    pub fn with_clock(clock: &Clock) -> Self {
+        let now = clock.now();
         Self {
-            id: Uuid::new_v4(),
-            bot_id: Uuid::new_v4(),
-            customer_id: Uuid::new_v4(),
+            id: clock.next_id(),
+            bot_id: clock.next_id(),
+            customer_id: clock.next_id(),
             channel: Channel::WhatsApp,
             state: SessionState::Active,
             context: HashMap::new(),
-            started_at: Utc::now(),
-            updated_at: Utc::now(),
+            started_at: now,
+            updated_at: now,
             ended_at: None,
         }
     }
 }
 
+impl Default for Session {
+    fn default() -> Self {
+        Self::with_clock(&default_clock())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[derive(Default)]
@@ -157,7 +198,6 @@ pub enum SessionState {
     Ended,
 }
 
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub id: Uuid,
@@ -169,20 +209,28 @@ pub struct Message {
     pub metadata: HashMap<String, serde_json::Value>,
 }
 
-impl Default for Message {
-    fn default() -> Self {
+impl Message {
+    #[must_use]
+    pub fn with_clock(clock: &Clock) -> Self {
+        let now = clock.now();
         Self {
-            id: Uuid::new_v4(),
-            session_id: Uuid::new_v4(),
+            id: clock.next_id(),
+            session_id: clock.next_id(),
             direction: MessageDirection::Incoming,
             content: "Hello".to_string(),
             content_type: ContentType::Text,
-            timestamp: Utc::now(),
+            timestamp: now,
             metadata: HashMap::new(),
         }
     }
 }
 
+impl Default for Message {
+    fn default() -> Self {
+        Self::with_clock(&default_clock())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum MessageDirection {
@@ -205,7 +253,6 @@ pub enum ContentType {
     Interactive,
 }
 
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueueEntry {
     pub id: Uuid,
@@ -218,21 +265,29 @@ pub struct QueueEntry {
     pub attendant_id: Option<Uuid>,
 }
 
-impl Default for QueueEntry {
-    fn default() -> Self {
+impl QueueEntry {
+    #[must_use]
+    pub fn with_clock(clock: &Clock) -> Self {
+        let now = clock.now();
         Self {
-            id: Uuid::new_v4(),
-            customer_id: Uuid::new_v4(),
-            session_id: Uuid::new_v4(),
+            id: clock.next_id(),
+            customer_id: clock.next_id(),
+            session_id: clock.next_id(),
             priority: Priority::Normal,
             status: QueueStatus::Waiting,
-            entered_at: Utc::now(),
+            entered_at: now,
             assigned_at: None,
             attendant_id: None,
         }
     }
 }
 
+impl Default for QueueEntry {
+    fn default() -> Self {
+        Self::with_clock(&default_clock())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 #[derive(Default)]
@@ -244,7 +299,6 @@ pub enum Priority {
     Urgent = 3,
 }
 
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[derive(Default)]
@@ -257,145 +311,219 @@ pub enum QueueStatus {
     Cancelled,
 }
 
-
 #[must_use]
 pub fn admin_user() -> User {
+    admin_user_with_clock(&default_clock())
+}
+
+#[must_use]
+pub fn admin_user_with_clock(clock: &Clock) -> User {
     User {
         email: "admin@test.com".to_string(),
         name: "Test Admin".to_string(),
         role: Role::Admin,
-        ..Default::default()
+        ..User::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn attendant_user() -> User {
+    attendant_user_with_clock(&default_clock())
+}
+
+#[must_use]
+pub fn attendant_user_with_clock(clock: &Clock) -> User {
     User {
         email: "attendant@test.com".to_string(),
         name: "Test Attendant".to_string(),
         role: Role::Attendant,
-        ..Default::default()
+        ..User::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn regular_user() -> User {
+    regular_user_with_clock(&default_clock())
+}
+
+#[must_use]
+pub fn regular_user_with_clock(clock: &Clock) -> User {
     User {
         email: "user@test.com".to_string(),
         name: "Test User".to_string(),
         role: Role::User,
-        ..Default::default()
+        ..User::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn user_with_email(email: &str) -> User {
+    user_with_email_with_clock(&default_clock(), email)
+}
+
+#[must_use]
+pub fn user_with_email_with_clock(clock: &Clock, email: &str) -> User {
     User {
         email: email.to_string(),
         name: email.split('@').next().unwrap_or("User").to_string(),
-        ..Default::default()
+        ..User::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn customer(phone: &str) -> Customer {
+    customer_with_clock(&default_clock(), phone)
+}
+
+#[must_use]
+pub fn customer_with_clock(clock: &Clock, phone: &str) -> Customer {
     Customer {
         phone: Some(phone.to_string()),
         channel: Channel::WhatsApp,
-        ..Default::default()
+        ..Customer::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn customer_on_channel(channel: Channel) -> Customer {
+    customer_on_channel_with_clock(&default_clock(), channel)
+}
+
+#[must_use]
+pub fn customer_on_channel_with_clock(clock: &Clock, channel: Channel) -> Customer {
     Customer {
         channel,
-        ..Default::default()
+        ..Customer::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn teams_customer() -> Customer {
+    teams_customer_with_clock(&default_clock())
+}
+
+#[must_use]
+pub fn teams_customer_with_clock(clock: &Clock) -> Customer {
     Customer {
         channel: Channel::Teams,
-        external_id: format!("teams_{}", Uuid::new_v4()),
-        ..Default::default()
+        external_id: format!("teams_{}", clock.next_id()),
+        ..Customer::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn web_customer() -> Customer {
+    web_customer_with_clock(&default_clock())
+}
+
+#[must_use]
+pub fn web_customer_with_clock(clock: &Clock) -> Customer {
     Customer {
         channel: Channel::Web,
-        external_id: format!("web_{}", Uuid::new_v4()),
-        ..Default::default()
+        external_id: format!("web_{}", clock.next_id()),
+        ..Customer::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn basic_bot(name: &str) -> Bot {
+    basic_bot_with_clock(&default_clock(), name)
+}
+
+#[must_use]
+pub fn basic_bot_with_clock(clock: &Clock, name: &str) -> Bot {
     Bot {
         name: name.to_string(),
         kb_enabled: false,
         llm_enabled: true,
-        ..Default::default()
+        ..Bot::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn bot_with_kb(name: &str) -> Bot {
+    bot_with_kb_with_clock(&default_clock(), name)
+}
+
+#[must_use]
+pub fn bot_with_kb_with_clock(clock: &Clock, name: &str) -> Bot {
     Bot {
         name: name.to_string(),
         kb_enabled: true,
         llm_enabled: true,
-        ..Default::default()
+        ..Bot::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn rule_based_bot(name: &str) -> Bot {
+    rule_based_bot_with_clock(&default_clock(), name)
+}
+
+#[must_use]
+pub fn rule_based_bot_with_clock(clock: &Clock, name: &str) -> Bot {
     Bot {
         name: name.to_string(),
         kb_enabled: false,
         llm_enabled: false,
         llm_model: None,
-        ..Default::default()
+        ..Bot::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn session_for(bot: &Bot, customer: &Customer) -> Session {
+    session_for_with_clock(&default_clock(), bot, customer)
+}
+
+#[must_use]
+pub fn session_for_with_clock(clock: &Clock, bot: &Bot, customer: &Customer) -> Session {
     Session {
         bot_id: bot.id,
         customer_id: customer.id,
         channel: customer.channel,
-        ..Default::default()
+        ..Session::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn active_session() -> Session {
+    active_session_with_clock(&default_clock())
+}
+
+#[must_use]
+pub fn active_session_with_clock(clock: &Clock) -> Session {
     Session {
         state: SessionState::Active,
-        ..Default::default()
+        ..Session::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn incoming_message(content: &str) -> Message {
+    incoming_message_with_clock(&default_clock(), content)
+}
+
+#[must_use]
+pub fn incoming_message_with_clock(clock: &Clock, content: &str) -> Message {
     Message {
         direction: MessageDirection::Incoming,
         content: content.to_string(),
-        ..Default::default()
+        ..Message::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn outgoing_message(content: &str) -> Message {
+    outgoing_message_with_clock(&default_clock(), content)
+}
+
+#[must_use]
+pub fn outgoing_message_with_clock(clock: &Clock, content: &str) -> Message {
     Message {
         direction: MessageDirection::Outgoing,
         content: content.to_string(),
-        ..Default::default()
+        ..Message::with_clock(clock)
     }
 }
 
@@ -404,37 +532,66 @@ pub fn message_in_session(
     session: &Session,
     content: &str,
     direction: MessageDirection,
+) -> Message {
+    message_in_session_with_clock(&default_clock(), session, content, direction)
+}
+
+#[must_use]
+pub fn message_in_session_with_clock(
+    clock: &Clock,
+    session: &Session,
+    content: &str,
+    direction: MessageDirection,
 ) -> Message {
     Message {
         session_id: session.id,
         direction,
         content: content.to_string(),
-        ..Default::default()
+        ..Message::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn queue_entry_for(customer: &Customer, session: &Session) -> QueueEntry {
+    queue_entry_for_with_clock(&default_clock(), customer, session)
+}
+
+#[must_use]
+pub fn queue_entry_for_with_clock(
+    clock: &Clock,
+    customer: &Customer,
+    session: &Session,
+) -> QueueEntry {
     QueueEntry {
         customer_id: customer.id,
         session_id: session.id,
-        ..Default::default()
+        ..QueueEntry::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn high_priority_queue_entry() -> QueueEntry {
+    high_priority_queue_entry_with_clock(&default_clock())
+}
+
+#[must_use]
+pub fn high_priority_queue_entry_with_clock(clock: &Clock) -> QueueEntry {
     QueueEntry {
         priority: Priority::High,
-        ..Default::default()
+        ..QueueEntry::with_clock(clock)
     }
 }
 
 #[must_use]
 pub fn urgent_queue_entry() -> QueueEntry {
+    urgent_queue_entry_with_clock(&default_clock())
+}
+
+#[must_use]
+pub fn urgent_queue_entry_with_clock(clock: &Clock) -> QueueEntry {
     QueueEntry {
         priority: Priority::Urgent,
-        ..Default::default()
+        ..QueueEntry::with_clock(clock)
     }
 }
 
@@ -504,4 +661,21 @@ mod tests {
         let _message = Message::default();
         let _queue = QueueEntry::default();
     }
+
+    #[test]
+    fn test_with_clock_variants_use_the_given_clock() {
+        let clock = Clock::seeded(42);
+        let first = admin_user_with_clock(&clock);
+        let first_bot = basic_bot_with_clock(&clock, "seeded");
+
+        let replay = Clock::seeded(42);
+        let second = admin_user_with_clock(&replay);
+        let second_bot = basic_bot_with_clock(&replay, "seeded");
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(first.created_at, clock.now());
+        assert_eq!(first_bot.id, second_bot.id);
+        assert_ne!(first.id, first_bot.id);
+        assert_ne!(admin_user().id, admin_user().id);
+    }
 }
@@ -0,0 +1,218 @@
+use super::{default_clock, is_seeded, Priority, QueueEntry, QueueStatus};
+use crate::mocks::Clock;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+pub const MINUTES_PER_POSITION: i64 = 2;
+
+#[derive(Debug, Clone)]
+pub struct QueueSimulator {
+    entries: Vec<QueueEntry>,
+    clock: Clock,
+}
+
+impl QueueSimulator {
+    #[must_use]
+    pub fn new() -> Self {
+        let clock = if is_seeded() {
+            default_clock()
+        } else {
+            Clock::frozen(Utc::now())
+        };
+        Self::new_with_clock(&clock)
+    }
+
+    #[must_use]
+    pub fn new_with_clock(clock: &Clock) -> Self {
+        Self {
+            entries: Vec::new(),
+            clock: clock.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    pub fn advance(&mut self, by: Duration) {
+        self.clock.advance(by.to_std().unwrap_or_default());
+    }
+
+    pub fn enqueue(&mut self, priority: Priority) -> Uuid {
+        self.enqueue_for(self.clock.next_id(), priority)
+    }
+
+    pub fn enqueue_for(&mut self, customer_id: Uuid, priority: Priority) -> Uuid {
+        let entry = QueueEntry {
+            id: self.clock.next_id(),
+            customer_id,
+            session_id: self.clock.next_id(),
+            priority,
+            status: QueueStatus::Waiting,
+            entered_at: self.now(),
+            assigned_at: None,
+            attendant_id: None,
+        };
+        let id = entry.id;
+        self.entries.push(entry);
+        id
+    }
+
+    pub fn enqueue_many(&mut self, count: usize, priority: Priority) -> Vec<Uuid> {
+        (0..count).map(|_| self.enqueue(priority)).collect()
+    }
+
+    #[must_use]
+    pub fn entries(&self) -> &[QueueEntry] {
+        &self.entries
+    }
+
+    #[must_use]
+    pub fn entry(&self, entry_id: Uuid) -> Option<&QueueEntry> {
+        self.entries.iter().find(|e| e.id == entry_id)
+    }
+
+    #[must_use]
+    pub fn waiting(&self) -> Vec<&QueueEntry> {
+        let mut waiting: Vec<&QueueEntry> = self
+            .entries
+            .iter()
+            .filter(|e| e.status == QueueStatus::Waiting)
+            .collect();
+        waiting.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then(a.entered_at.cmp(&b.entered_at))
+        });
+        waiting
+    }
+
+    #[must_use]
+    pub fn position(&self, entry_id: Uuid) -> Option<usize> {
+        self.waiting()
+            .iter()
+            .position(|e| e.id == entry_id)
+            .map(|index| index + 1)
+    }
+
+    #[must_use]
+    pub fn position_of_customer(&self, customer_id: Uuid) -> Option<usize> {
+        self.waiting()
+            .iter()
+            .position(|e| e.customer_id == customer_id)
+            .map(|index| index + 1)
+    }
+
+    #[must_use]
+    pub fn estimated_wait(&self, entry_id: Uuid) -> Option<Duration> {
+        self.position(entry_id)
+            .map(|position| Duration::minutes(position as i64 * MINUTES_PER_POSITION))
+    }
+
+    #[must_use]
+    pub fn time_waited(&self, entry_id: Uuid) -> Option<Duration> {
+        let entry = self.entry(entry_id)?;
+        Some(entry.assigned_at.unwrap_or_else(|| self.now()) - entry.entered_at)
+    }
+
+    #[must_use]
+    pub fn expected_messages(&self, entry_id: Uuid) -> Vec<String> {
+        match (self.position(entry_id), self.estimated_wait(entry_id)) {
+            (Some(position), Some(wait)) => vec![
+                format!("You are number {position} in the queue."),
+                format!("Estimated wait time: {} minutes.", wait.num_minutes()),
+            ],
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn assign_next(&mut self, attendant_id: Uuid) -> Option<QueueEntry> {
+        let next = self.waiting().first()?.id;
+        let now = self.now();
+        let entry = self.entries.iter_mut().find(|e| e.id == next)?;
+        entry.status = QueueStatus::Assigned;
+        entry.assigned_at = Some(now);
+        entry.attendant_id = Some(attendant_id);
+        Some(entry.clone())
+    }
+
+    pub fn cancel(&mut self, entry_id: Uuid) -> bool {
+        match self
+            .entries
+            .iter_mut()
+            .find(|e| e.id == entry_id && e.status == QueueStatus::Waiting)
+        {
+            Some(entry) => {
+                entry.status = QueueStatus::Cancelled;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Default for QueueSimulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_waiting_orders_by_priority_then_arrival() {
+        let mut queue = QueueSimulator::new();
+        let low = queue.enqueue(Priority::Low);
+        queue.advance(Duration::seconds(10));
+        let normal = queue.enqueue_many(2, Priority::Normal);
+        queue.advance(Duration::seconds(10));
+        let urgent = queue.enqueue(Priority::Urgent);
+        let high = queue.enqueue(Priority::High);
+
+        let order: Vec<Uuid> = queue.waiting().iter().map(|e| e.id).collect();
+        assert_eq!(order, vec![urgent, high, normal[0], normal[1], low]);
+        assert_eq!(queue.position(urgent), Some(1));
+        assert_eq!(queue.position(low), Some(5));
+    }
+
+    #[test]
+    fn test_positions_and_wait_after_assignment() {
+        let clock = Clock::seeded(3);
+        let mut queue = QueueSimulator::new_with_clock(&clock);
+        let ids = queue.enqueue_many(3, Priority::Normal);
+        let customer = clock.next_id();
+        let vip = queue.enqueue_for(customer, Priority::High);
+
+        assert_eq!(queue.position_of_customer(customer), Some(1));
+        assert_eq!(queue.position(ids[2]), Some(4));
+        assert_eq!(queue.estimated_wait(ids[2]), Some(Duration::minutes(8)));
+        assert_eq!(
+            queue.expected_messages(ids[0]),
+            vec![
+                "You are number 2 in the queue.",
+                "Estimated wait time: 4 minutes."
+            ]
+        );
+
+        queue.advance(Duration::minutes(3));
+        let attendant = clock.next_id();
+        let assigned = queue.assign_next(attendant).unwrap();
+        assert_eq!(assigned.id, vip);
+        assert_eq!(assigned.status, QueueStatus::Assigned);
+        assert_eq!(assigned.attendant_id, Some(attendant));
+
+        assert!(queue.cancel(ids[0]));
+        assert!(!queue.cancel(vip));
+        assert_eq!(queue.position(vip), None);
+        assert_eq!(queue.position(ids[0]), None);
+        assert_eq!(queue.position(ids[2]), Some(2));
+        assert!(queue.expected_messages(vip).is_empty());
+
+        queue.advance(Duration::minutes(2));
+        assert_eq!(queue.time_waited(vip), Some(Duration::minutes(3)));
+        assert_eq!(queue.time_waited(ids[1]), Some(Duration::minutes(5)));
+    }
+}
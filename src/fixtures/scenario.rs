@@ -0,0 +1,202 @@
+use super::{
+    customer_on_channel_with_clock, default_clock, incoming_message_with_clock,
+    session_for_with_clock, Bot, Channel, Customer, Message, Session,
+};
+use crate::harness::Insertable;
+use crate::mocks::Clock;
+
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub bot: Bot,
+    pub customer: Customer,
+    pub session: Option<Session>,
+    pub messages: Vec<Message>,
+    clock: Clock,
+}
+
+impl Scenario {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::new_with_clock(&default_clock())
+    }
+
+    #[must_use]
+    pub fn new_with_clock(clock: &Clock) -> Self {
+        Self {
+            bot: Bot::with_clock(clock),
+            customer: Customer::with_clock(clock),
+            session: None,
+            messages: Vec::new(),
+            clock: clock.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn bot(mut self, name: &str) -> Self {
+        self.bot.name = name.to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn with_bot(mut self, bot: Bot) -> Self {
+        self.bot = bot;
+        self.wire();
+        self
+    }
+
+    #[must_use]
+    pub fn customer_on(self, channel: Channel) -> Self {
+        let customer = customer_on_channel_with_clock(&self.clock, channel);
+        self.with_customer(customer)
+    }
+
+    #[must_use]
+    pub fn with_customer(mut self, customer: Customer) -> Self {
+        self.customer = customer;
+        self.wire();
+        self
+    }
+
+    #[must_use]
+    pub fn with_session(mut self) -> Self {
+        if self.session.is_none() {
+            self.session = Some(session_for_with_clock(
+                &self.clock,
+                &self.bot,
+                &self.customer,
+            ));
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn with_messages<I, S>(mut self, contents: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self = self.with_session();
+        self.messages.extend(
+            contents
+                .into_iter()
+                .map(|c| incoming_message_with_clock(&self.clock, c.as_ref())),
+        );
+        self.wire();
+        self
+    }
+
+    #[must_use]
+    pub fn session(&self) -> &Session {
+        self.session
+            .as_ref()
+            .expect("Scenario has no session; call with_session() first")
+    }
+
+    #[must_use]
+    pub fn entities(&self) -> Vec<&dyn Insertable> {
+        let mut entities: Vec<&dyn Insertable> = vec![&self.bot, &self.customer];
+        if let Some(ref session) = self.session {
+            entities.push(session);
+        }
+        entities.extend(self.messages.iter().map(|m| m as &dyn Insertable));
+        entities
+    }
+
+    #[must_use]
+    pub fn to_sql(&self) -> String {
+        self.entities()
+            .iter()
+            .map(|entity| entity.to_sql())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn wire(&mut self) {
+        if let Some(ref mut session) = self.session {
+            session.bot_id = self.bot.id;
+            session.customer_id = self.customer.id;
+            session.channel = self.customer.channel;
+            for message in &mut self.messages {
+                message.session_id = session.id;
+            }
+        }
+    }
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{bot_with_kb, MessageDirection};
+
+    #[test]
+    fn test_scenario_wires_foreign_keys() {
+        let scenario = Scenario::new()
+            .bot("sales")
+            .customer_on(Channel::WhatsApp)
+            .with_session()
+            .with_messages(["hi", "help"]);
+
+        let session = scenario.session();
+        assert_eq!(scenario.bot.name, "sales");
+        assert_eq!(session.bot_id, scenario.bot.id);
+        assert_eq!(session.customer_id, scenario.customer.id);
+        assert_eq!(session.channel, Channel::WhatsApp);
+
+        let contents: Vec<&str> = scenario
+            .messages
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(contents, vec!["hi", "help"]);
+        assert!(scenario
+            .messages
+            .iter()
+            .all(|m| m.session_id == session.id && m.direction == MessageDirection::Incoming));
+    }
+
+    #[test]
+    fn test_scenario_rewires_after_replacing_entities() {
+        let scenario = Scenario::new()
+            .with_messages(["hello"])
+            .with_bot(bot_with_kb("kb"))
+            .customer_on(Channel::Teams);
+
+        let session = scenario.session();
+        assert_eq!(session.bot_id, scenario.bot.id);
+        assert_eq!(session.customer_id, scenario.customer.id);
+        assert_eq!(session.channel, Channel::Teams);
+        assert_eq!(scenario.messages[0].session_id, session.id);
+        assert!(Scenario::new().session.is_none());
+    }
+
+    #[test]
+    fn test_scenario_renders_insert_sql() {
+        let scenario = Scenario::new()
+            .bot("sales")
+            .customer_on(Channel::WhatsApp)
+            .with_session()
+            .with_messages(["it's me"]);
+
+        let sql = scenario.to_sql();
+        let statements: Vec<&str> = sql.lines().collect();
+        assert_eq!(statements.len(), 4);
+        assert!(statements[0].starts_with("INSERT INTO bots "));
+        assert!(statements[1].starts_with("INSERT INTO customers "));
+        assert!(statements[2].starts_with("INSERT INTO sessions "));
+        assert!(statements[3].starts_with("INSERT INTO messages "));
+        assert!(statements
+            .iter()
+            .all(|s| s.ends_with(';') && !s.contains('$')));
+
+        assert!(statements[0].contains("'sales'"));
+        assert!(statements[1].contains("'whatsapp'"));
+        assert!(statements[2].contains(&format!("'{}'", scenario.bot.id)));
+        assert!(statements[3].contains("'it''s me'"));
+    }
+}
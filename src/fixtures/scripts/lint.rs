@@ -0,0 +1,285 @@
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+    UnclosedBlock,
+    UnexpectedBlockEnd,
+    UndefinedLabel,
+    UnbalancedGosub,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    pub line: usize,
+    pub kind: LintKind,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn new(line: usize, kind: LintKind, message: String) -> Self {
+        Self {
+            line,
+            kind,
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    If,
+    For,
+    Select,
+    Transaction,
+}
+
+impl Block {
+    const fn opener(self) -> &'static str {
+        match self {
+            Self::If => "IF",
+            Self::For => "FOR",
+            Self::Select => "SELECT CASE",
+            Self::Transaction => "BEGIN TRANSACTION",
+        }
+    }
+
+    const fn closer(self) -> &'static str {
+        match self {
+            Self::If => "END IF",
+            Self::For => "NEXT",
+            Self::Select => "END SELECT",
+            Self::Transaction => "COMMIT TRANSACTION",
+        }
+    }
+}
+
+fn strip_strings(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_string = false;
+    for c in line.chars() {
+        if c == '"' {
+            in_string = !in_string;
+            out.push('"');
+        } else if !in_string {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn is_comment(line: &str) -> bool {
+    let upper = line.to_uppercase();
+    line.starts_with('\'') || upper == "REM" || upper.starts_with("REM ")
+}
+
+fn label_definition(line: &str) -> Option<&str> {
+    let label = line.strip_suffix(':')?;
+    let mut chars = label.chars();
+    let first = chars.next()?;
+    ((first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_'))
+    .then_some(label)
+}
+
+#[must_use]
+pub fn validate(source: &str) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    let mut stack: Vec<(Block, usize)> = Vec::new();
+    let mut labels = HashSet::new();
+    let mut jumps: Vec<(usize, String)> = Vec::new();
+    let mut gosubs = Vec::new();
+    let mut returns = Vec::new();
+
+    for (index, raw) in source.lines().enumerate() {
+        let line_no = index + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || is_comment(trimmed) {
+            continue;
+        }
+
+        if let Some(label) = label_definition(trimmed) {
+            labels.insert(label.to_string());
+            continue;
+        }
+
+        let code = strip_strings(trimmed);
+        let tokens: Vec<String> = code.split_whitespace().map(str::to_uppercase).collect();
+        let first = tokens.first().map_or("", String::as_str);
+        let second = tokens.get(1).map_or("", String::as_str);
+
+        for (i, token) in tokens.iter().enumerate() {
+            if token == "GOTO" || token == "GOSUB" {
+                if let Some(target) = code.split_whitespace().nth(i + 1) {
+                    jumps.push((line_no, target.to_string()));
+                }
+                if token == "GOSUB" {
+                    gosubs.push(line_no);
+                }
+            }
+        }
+
+        let opened = match (first, second) {
+            ("IF", _) if tokens.last().is_some_and(|t| t == "THEN") => Some(Block::If),
+            ("FOR", _) => Some(Block::For),
+            ("SELECT", "CASE") => Some(Block::Select),
+            ("BEGIN", "TRANSACTION") => Some(Block::Transaction),
+            _ => None,
+        };
+        if let Some(block) = opened {
+            stack.push((block, line_no));
+            continue;
+        }
+
+        let closed = match (first, second) {
+            ("END", "IF") => Some(Block::If),
+            ("NEXT", _) => Some(Block::For),
+            ("END", "SELECT") => Some(Block::Select),
+            ("COMMIT", "TRANSACTION") => Some(Block::Transaction),
+            _ => None,
+        };
+        if let Some(block) = closed {
+            match stack.iter().rposition(|(open, _)| *open == block) {
+                Some(position) => {
+                    for (open, opened_at) in stack.drain(position + 1..) {
+                        issues.push(LintIssue::new(
+                            opened_at,
+                            LintKind::UnclosedBlock,
+                            format!(
+                                "{} is never closed with {} before {} at line {line_no}",
+                                open.opener(),
+                                open.closer(),
+                                block.closer()
+                            ),
+                        ));
+                    }
+                    stack.pop();
+                }
+                None => {
+                    issues.push(LintIssue::new(
+                        line_no,
+                        LintKind::UnexpectedBlockEnd,
+                        format!("{} without matching {}", block.closer(), block.opener()),
+                    ));
+                }
+            }
+            continue;
+        }
+
+        if (first == "ELSE" || first == "ELSEIF")
+            && !stack.last().is_some_and(|(b, _)| *b == Block::If)
+        {
+            issues.push(LintIssue::new(
+                line_no,
+                LintKind::UnexpectedBlockEnd,
+                format!("{first} outside of an IF block"),
+            ));
+        }
+
+        if first == "RETURN" {
+            returns.push(line_no);
+        }
+    }
+
+    for (block, opened_at) in stack {
+        issues.push(LintIssue::new(
+            opened_at,
+            LintKind::UnclosedBlock,
+            format!("{} is never closed with {}", block.opener(), block.closer()),
+        ));
+    }
+
+    for (line_no, target) in jumps {
+        if !labels.contains(&target) {
+            issues.push(LintIssue::new(
+                line_no,
+                LintKind::UndefinedLabel,
+                format!("Jump to undefined label '{target}'"),
+            ));
+        }
+    }
+
+    match (gosubs.first(), returns.first()) {
+        (Some(&line_no), None) => issues.push(LintIssue::new(
+            line_no,
+            LintKind::UnbalancedGosub,
+            "GOSUB used but no subroutine ends with RETURN".to_string(),
+        )),
+        (None, Some(&line_no)) => issues.push(LintIssue::new(
+            line_no,
+            LintKind::UnbalancedGosub,
+            "RETURN without any GOSUB".to_string(),
+        )),
+        _ => {}
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::scripts::{available_scripts, get_script};
+
+    #[test]
+    fn test_sample_scripts_are_valid() {
+        for name in available_scripts() {
+            let issues = validate(get_script(name).unwrap());
+            assert!(issues.is_empty(), "{name}: {issues:?}");
+        }
+    }
+
+    #[test]
+    fn test_unclosed_if_reported() {
+        let source = "HEAR x$\nIF x$ = \"a\" THEN\n    TALK \"a\"\nFOR i = 1 TO 3\nNEXT i\n";
+        let issues = validate(source);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, LintKind::UnclosedBlock);
+        assert_eq!(issues[0].line, 2);
+        assert_eq!(
+            issues[0].to_string(),
+            "line 2: IF is never closed with END IF"
+        );
+    }
+
+    #[test]
+    fn test_mismatched_and_stray_block_ends() {
+        let source = "FOR i = 1 TO 3\n    IF i > 1 THEN\nNEXT i\nEND SELECT\n";
+        let issues = validate(source);
+
+        let kinds: Vec<LintKind> = issues.iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![LintKind::UnclosedBlock, LintKind::UnexpectedBlockEnd]
+        );
+        assert_eq!(issues[0].line, 2);
+        assert!(issues[0].message.contains("before NEXT at line 3"));
+        assert_eq!(issues[1].line, 4);
+    }
+
+    #[test]
+    fn test_undefined_label_and_gosub_balance() {
+        let source = "start:\nIF x THEN GOTO finish\nGOSUB helper\nGOTO start\nhelper:\nTALK \"GOTO nowhere\"\n";
+        let issues = validate(source);
+
+        let kinds: Vec<LintKind> = issues.iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![LintKind::UndefinedLabel, LintKind::UnbalancedGosub]
+        );
+        assert!(issues[0].message.contains("finish"));
+        assert_eq!(issues[1].line, 3);
+    }
+
+    #[test]
+    fn test_single_line_if_does_not_open_block() {
+        assert!(validate("IF x > 1 THEN TALK \"big\"\nON ERROR RESUME NEXT\n").is_empty());
+    }
+}
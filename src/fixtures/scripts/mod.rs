@@ -1,4 +1,10 @@
 
+mod lint;
+mod template;
+
+pub use lint::{validate, LintIssue, LintKind};
+pub use template::{placeholders, render, render_strict};
+
 use std::collections::HashMap;
 
 #[must_use]
@@ -50,7 +56,7 @@ pub const GREETING_SCRIPT: &str = r#"
 ' Simple greeting and response pattern
 
 REM Initialize greeting
-greeting$ = "Hello! Welcome to our service."
+greeting$ = "${GREETING}"
 TALK greeting$
 
 REM Wait for user response
@@ -254,7 +260,7 @@ payload.name = "Test Order"
 payload.items = ["item1", "item2"]
 payload.total = 150.00
 
-response = POST "https://api.example.com/orders" BODY payload HEADERS "Content-Type: application/json"
+response = POST "${API_BASE_URL}/orders" BODY payload HEADERS "Content-Type: application/json"
 
 IF response.status = 200 THEN
     TALK "Order created: " + response.body.order_id
@@ -266,12 +272,12 @@ REM GraphQL query
 query$ = "query GetUser($id: ID!) { user(id: $id) { name email } }"
 variables.id = "12345"
 
-gql_response = GRAPHQL "https://api.example.com/graphql" QUERY query$ VARIABLES variables
+gql_response = GRAPHQL "${API_BASE_URL}/graphql" QUERY query$ VARIABLES variables
 TALK "User: " + gql_response.data.user.name
 
 REM SOAP request
 soap_body$ = "<GetProduct><SKU>ABC123</SKU></GetProduct>"
-soap_response = SOAP "https://api.example.com/soap" ACTION "GetProduct" BODY soap_body$
+soap_response = SOAP "${API_BASE_URL}/soap" ACTION "GetProduct" BODY soap_body$
 TALK "Product: " + soap_response.ProductName
 "#;
 
@@ -0,0 +1,119 @@
+use super::get_script;
+use anyhow::Result;
+use std::collections::HashMap;
+
+fn is_placeholder_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn substitute(source: &str, vars: &HashMap<&str, &str>) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) if is_placeholder_name(&after[..end]) => {
+                let name = &after[..end];
+                match vars.get(name) {
+                    Some(value) => out.push_str(value),
+                    None => {
+                        out.push_str("${");
+                        out.push_str(name);
+                        out.push('}');
+                    }
+                }
+                rest = &after[end + 1..];
+            }
+            _ => {
+                out.push_str("${");
+                rest = after;
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[must_use]
+pub fn placeholders(source: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = source;
+
+    while let Some(start) = rest.find("${") {
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) if is_placeholder_name(&after[..end]) => {
+                let name = &after[..end];
+                if !names.iter().any(|n| n == name) {
+                    names.push(name.to_string());
+                }
+                rest = &after[end + 1..];
+            }
+            _ => rest = after,
+        }
+    }
+
+    names
+}
+
+#[must_use]
+pub fn render(name: &str, vars: &HashMap<&str, &str>) -> Option<String> {
+    get_script(name).map(|source| substitute(source, vars))
+}
+
+pub fn render_strict(name: &str, vars: &HashMap<&str, &str>) -> Result<String> {
+    let Some(rendered) = render(name, vars) else {
+        anyhow::bail!("Unknown script '{name}'");
+    };
+
+    let unresolved = placeholders(&rendered);
+    if !unresolved.is_empty() {
+        anyhow::bail!(
+            "Script '{name}' has unresolved placeholders: {}",
+            unresolved.join(", ")
+        );
+    }
+
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_replaces_vars_and_reports_unknown() {
+        let vars = HashMap::from([
+            ("API_KEY", "secret-123"),
+            ("API_BASE_URL", "http://mock:9000"),
+        ]);
+
+        let rendered = render("http_integration", &vars).unwrap();
+        assert!(rendered.contains("Bearer secret-123"));
+        assert!(rendered.contains("POST \"http://mock:9000/orders\""));
+        assert!(placeholders(&rendered).is_empty());
+        assert!(rendered.contains("GetUser($id: ID!)"));
+
+        let partial = render("greeting", &vars).unwrap();
+        assert_eq!(placeholders(&partial), vec!["GREETING"]);
+        let err = render_strict("greeting", &vars).unwrap_err().to_string();
+        assert!(err.contains("unresolved placeholders: GREETING"));
+
+        assert!(render("nonexistent", &vars).is_none());
+    }
+
+    #[test]
+    fn test_placeholders_ignore_malformed() {
+        let source = "a ${ONE} b ${ONE} c ${not closed d ${TWO}$ {THREE}";
+        assert_eq!(placeholders(source), vec!["ONE", "TWO"]);
+
+        let vars = HashMap::from([("ONE", "1")]);
+        assert_eq!(
+            substitute(source, &vars),
+            "a 1 b 1 c ${not closed d ${TWO}$ {THREE}"
+        );
+    }
+}
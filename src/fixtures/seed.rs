@@ -0,0 +1,63 @@
+use crate::mocks::Clock;
+use std::sync::Mutex;
+
+static SEEDED_CLOCK: Mutex<Option<Clock>> = Mutex::new(None);
+
+pub fn with_seed(seed: u64) {
+    *SEEDED_CLOCK.lock().unwrap() = Some(Clock::seeded(seed));
+}
+
+pub fn reset_seed() {
+    *SEEDED_CLOCK.lock().unwrap() = None;
+}
+
+#[must_use]
+pub fn is_seeded() -> bool {
+    SEEDED_CLOCK.lock().unwrap().is_some()
+}
+
+#[must_use]
+pub(crate) fn default_clock() -> Clock {
+    SEEDED_CLOCK
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(Clock::system)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{admin_user, basic_bot};
+
+    #[test]
+    fn test_same_seed_produces_same_ids() {
+        with_seed(42);
+        let first = admin_user();
+        let first_bot = basic_bot("seeded");
+
+        with_seed(42);
+        let second = admin_user();
+        let second_bot = basic_bot("seeded");
+        reset_seed();
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(first.created_at, second.created_at);
+        assert_eq!(first_bot.id, second_bot.id);
+        assert_ne!(first.id, first_bot.id);
+        assert_eq!(first.id.get_version_num(), 4);
+    }
+
+    #[test]
+    fn test_seed_is_shared_across_threads() {
+        with_seed(7);
+        let here = admin_user();
+        with_seed(7);
+        let there = std::thread::spawn(admin_user).join().unwrap();
+        reset_seed();
+
+        assert_eq!(here.id, there.id);
+        assert!(!is_seeded());
+        assert_ne!(admin_user().id, admin_user().id);
+    }
+}
@@ -2,8 +2,10 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
 
+pub mod asserts;
 pub mod bot;
 pub mod desktop;
+mod error;
 pub mod fixtures;
 mod harness;
 pub mod mocks;
@@ -11,16 +13,21 @@ mod ports;
 pub mod services;
 pub mod web;
 
+pub use error::HarnessError;
 pub use harness::{
-    BotServerInstance, BotUIInstance, Insertable, TestConfig, TestContext, TestHarness,
+    BotServerInstance, BotServerOptions, BotUIInstance, DbPool, Insertable, MockKind,
+    TestCertificate, TestConfig, TestConfigBuilder, TestContext, TestHarness,
 };
 pub use ports::PortAllocator;
 
 pub mod prelude {
+    pub use crate::asserts::*;
     pub use crate::bot::*;
+    pub use crate::error::HarnessError;
     pub use crate::fixtures::*;
     pub use crate::harness::{
-        BotServerInstance, BotUIInstance, Insertable, TestConfig, TestContext, TestHarness,
+        BotServerInstance, BotServerOptions, BotUIInstance, DbPool, Insertable, MockKind,
+        TestCertificate, TestConfig, TestConfigBuilder, TestContext, TestHarness,
     };
     pub use crate::mocks::*;
     pub use crate::services::*;
@@ -0,0 +1,84 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use wiremock::Request;
+
+pub trait RequestAssertions {
+    fn count_calls(&self, method: &str, path_regex: &str) -> Result<usize>;
+
+    fn assert_called(&self, method: &str, path_regex: &str, times: usize) -> Result<()>;
+
+    fn assert_not_called(&self, method: &str, path_regex: &str) -> Result<()> {
+        self.assert_called(method, path_regex, 0)
+    }
+}
+
+impl RequestAssertions for [Request] {
+    fn count_calls(&self, method: &str, path_regex: &str) -> Result<usize> {
+        let pattern =
+            Regex::new(path_regex).with_context(|| format!("Invalid path regex: {path_regex}"))?;
+
+        Ok(self
+            .iter()
+            .filter(|req| req.method.to_string().eq_ignore_ascii_case(method))
+            .filter(|req| pattern.is_match(req.url.path()))
+            .count())
+    }
+
+    fn assert_called(&self, method: &str, path_regex: &str, times: usize) -> Result<()> {
+        let actual = self.count_calls(method, path_regex)?;
+        if actual != times {
+            let received: Vec<String> = self
+                .iter()
+                .map(|req| format!("{} {}", req.method, req.url.path()))
+                .collect();
+            anyhow::bail!(
+                "Expected {times} {} request(s) matching '{path_regex}' but got {actual}; received: [{}]",
+                method.to_uppercase(),
+                received.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_assert_called_counts_matching_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::new();
+        for _ in 0..2 {
+            client
+                .post(format!("{}/v1/messages", server.uri()))
+                .send()
+                .await
+                .unwrap();
+        }
+
+        let requests = server.received_requests().await.unwrap_or_default();
+        assert!(requests.assert_called("POST", "^/v1/messages$", 2).is_ok());
+        assert!(requests.assert_called("post", r"^/v1/\w+$", 2).is_ok());
+        assert!(requests.assert_not_called("GET", "^/v1/messages$").is_ok());
+        assert!(requests.assert_not_called("POST", "/v2/").is_ok());
+
+        let err = requests
+            .assert_called("POST", "^/v1/messages$", 3)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Expected 3 POST request(s)"));
+        assert!(err.contains("got 2"));
+        assert!(err.contains("POST /v1/messages"));
+
+        assert!(requests.assert_called("POST", "(", 1).is_err());
+    }
+}
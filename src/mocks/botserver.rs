@@ -0,0 +1,343 @@
+use super::delay::{delayed, ResponseDelay};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+pub struct StubBotServer {
+    server: MockServer,
+    port: u16,
+    replies: Arc<Mutex<Vec<(String, String)>>>,
+    turns: Arc<Mutex<Vec<ChatTurn>>>,
+    bots: Arc<Mutex<Vec<StubBot>>>,
+    delay: ResponseDelay,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatTurn {
+    pub bot: String,
+    pub message: String,
+    pub reply: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StubBot {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Deserialize)]
+struct ChatRequest {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct CreateBotRequest {
+    name: String,
+    #[serde(default)]
+    description: String,
+}
+
+const CHAT_PAGE: &str = r#"<!doctype html>
+<html>
+<body>
+  <div class="messages"></div>
+  <input id="chat-input" type="text" name="message">
+  <button id="send" type="button">Send</button>
+  <script>
+    const bot = location.pathname.split('/').pop();
+    document.getElementById('send').addEventListener('click', async () => {
+      const input = document.getElementById('chat-input');
+      const text = input.value;
+      input.value = '';
+      const messages = document.querySelector('.messages');
+      const user = document.createElement('div');
+      user.className = 'user-message';
+      user.textContent = text;
+      messages.appendChild(user);
+      const typing = document.createElement('div');
+      typing.className = 'typing-indicator';
+      messages.appendChild(typing);
+      const response = await fetch('/api/bots/' + bot + '/chat', {
+        method: 'POST',
+        headers: {'Content-Type': 'application/json'},
+        body: JSON.stringify({message: text})
+      });
+      const body = await response.json();
+      typing.remove();
+      const reply = document.createElement('div');
+      reply.className = 'bot-message';
+      reply.textContent = body.content;
+      messages.appendChild(reply);
+    });
+  </script>
+</body>
+</html>"#;
+
+impl StubBotServer {
+    pub async fn start(port: u16) -> Result<Self> {
+        let listener = std::net::TcpListener::bind(format!("127.0.0.1:{port}"))
+            .context("Failed to bind StubBotServer port")?;
+
+        let server = MockServer::builder().listener(listener).start().await;
+
+        let stub = Self {
+            server,
+            port,
+            replies: Arc::new(Mutex::new(Vec::new())),
+            turns: Arc::new(Mutex::new(Vec::new())),
+            bots: Arc::new(Mutex::new(Vec::new())),
+            delay: ResponseDelay::default(),
+        };
+
+        stub.setup_default_routes().await;
+
+        Ok(stub)
+    }
+
+    async fn setup_default_routes(&self) {
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "status": "ok",
+                    "stub": true
+                })),
+            ))
+            .mount(&self.server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/chat/[^/]+$"))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Type", "text/html")
+                    .set_body_string(CHAT_PAGE),
+            ))
+            .mount(&self.server)
+            .await;
+
+        let replies = self.replies.clone();
+        let turns = self.turns.clone();
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/api/bots/[^/]+/chat$"))
+            .respond_with(delayed(&self.delay, move |req: &wiremock::Request| {
+                let Ok(request) = serde_json::from_slice::<ChatRequest>(&req.body) else {
+                    return ResponseTemplate::new(400)
+                        .set_body_json(serde_json::json!({"error": "message is required"}));
+                };
+                let bot = req
+                    .url
+                    .path_segments()
+                    .and_then(|mut segments| segments.nth(2))
+                    .unwrap_or_default()
+                    .to_string();
+                let reply = replies
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|(pattern, _)| request.message.contains(pattern.as_str()))
+                    .map_or_else(
+                        || format!("You said: {}", request.message),
+                        |(_, reply)| reply.clone(),
+                    );
+
+                turns.lock().unwrap().push(ChatTurn {
+                    bot: bot.clone(),
+                    message: request.message,
+                    reply: reply.clone(),
+                });
+
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "bot": bot,
+                    "content": reply
+                }))
+            }))
+            .mount(&self.server)
+            .await;
+
+        let bots = self.bots.clone();
+        Mock::given(method("GET"))
+            .and(path("/api/bots"))
+            .respond_with(delayed(&self.delay, move |_: &wiremock::Request| {
+                ResponseTemplate::new(200).set_body_json(bots.lock().unwrap().clone())
+            }))
+            .mount(&self.server)
+            .await;
+
+        let bots = self.bots.clone();
+        Mock::given(method("POST"))
+            .and(path("/api/bots"))
+            .respond_with(delayed(&self.delay, move |req: &wiremock::Request| {
+                let Ok(request) = serde_json::from_slice::<CreateBotRequest>(&req.body) else {
+                    return ResponseTemplate::new(400)
+                        .set_body_json(serde_json::json!({"error": "name is required"}));
+                };
+                let bot = StubBot {
+                    id: Uuid::new_v4().to_string(),
+                    name: request.name,
+                    description: request.description,
+                };
+                bots.lock().unwrap().push(bot.clone());
+                ResponseTemplate::new(201).set_body_json(bot)
+            }))
+            .mount(&self.server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/queue"))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([])),
+            ))
+            .mount(&self.server)
+            .await;
+    }
+
+    pub fn reply_to(&self, pattern: &str, reply: &str) {
+        self.replies
+            .lock()
+            .unwrap()
+            .push((pattern.to_string(), reply.to_string()));
+    }
+
+    pub fn add_bot(&self, name: &str, description: &str) -> StubBot {
+        let bot = StubBot {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+        };
+        self.bots.lock().unwrap().push(bot.clone());
+        bot
+    }
+
+    #[must_use]
+    pub fn bots(&self) -> Vec<StubBot> {
+        self.bots.lock().unwrap().clone()
+    }
+
+    #[must_use]
+    pub fn chat_turns(&self) -> Vec<ChatTurn> {
+        self.turns.lock().unwrap().clone()
+    }
+
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    #[must_use]
+    pub fn chat_url(&self, bot: &str) -> String {
+        format!("{}/chat/{bot}", self.url())
+    }
+
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    #[must_use]
+    pub const fn response_delay(&self) -> &ResponseDelay {
+        &self.delay
+    }
+
+    pub async fn reset(&self) {
+        self.server.reset().await;
+        self.replies.lock().unwrap().clear();
+        self.turns.lock().unwrap().clear();
+        self.bots.lock().unwrap().clear();
+        self.setup_default_routes().await;
+    }
+
+    pub async fn received_requests(&self) -> Vec<wiremock::Request> {
+        self.server.received_requests().await.unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::ConversationRunner;
+    use crate::ports::PortAllocator;
+
+    #[tokio::test]
+    async fn test_stub_serves_health_chat_and_bots() {
+        let stub = StubBotServer::start(PortAllocator::allocate())
+            .await
+            .unwrap();
+        stub.reply_to("hours", "We are open 9am to 5pm.");
+        stub.add_bot("support", "Support bot");
+        let client = reqwest::Client::new();
+
+        let health = client
+            .get(format!("{}/health", stub.url()))
+            .send()
+            .await
+            .unwrap();
+        assert!(health.status().is_success());
+
+        let mut replies = Vec::new();
+        for message in ["What are your hours?", "hello"] {
+            let body: serde_json::Value = client
+                .post(format!("{}/api/bots/support/chat", stub.url()))
+                .json(&serde_json::json!({"message": message}))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            replies.push(body["content"].as_str().unwrap().to_string());
+        }
+        assert_eq!(replies, vec!["We are open 9am to 5pm.", "You said: hello"]);
+        assert_eq!(stub.chat_turns()[1].bot, "support");
+
+        let created = client
+            .post(format!("{}/api/bots", stub.url()))
+            .json(&serde_json::json!({"name": "sales"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(created.status().as_u16(), 201);
+        let bots: Vec<StubBot> = client
+            .get(format!("{}/api/bots", stub.url()))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let names: Vec<&str> = bots.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["support", "sales"]);
+
+        let page = client.get(stub.chat_url("support")).send().await.unwrap();
+        assert!(page.text().await.unwrap().contains("chat-input"));
+
+        stub.reset().await;
+        assert!(stub.chat_turns().is_empty());
+        assert!(stub.bots().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_conversation_runner_talks_to_stub() {
+        let stub = StubBotServer::start(PortAllocator::allocate())
+            .await
+            .unwrap();
+        stub.reply_to("hours", "We are open 9am to 5pm.");
+
+        let mut runner = ConversationRunner::new(&stub.url(), "support");
+        let reply = runner.send("What are your hours?").await.unwrap();
+        assert_eq!(reply.content, "We are open 9am to 5pm.");
+        let reply = runner.send("hello").await.unwrap();
+        assert_eq!(reply.content, "You said: hello");
+
+        let turns = stub.chat_turns();
+        assert_eq!(turns.len(), 2);
+        assert!(turns.iter().all(|turn| turn.bot == "support"));
+    }
+}
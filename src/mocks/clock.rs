@@ -0,0 +1,140 @@
+use chrono::{DateTime, TimeZone, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+const SEEDED_EPOCH_SECS: i64 = 1_704_067_200;
+
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+    frozen_at: Arc<Mutex<Option<DateTime<Utc>>>>,
+    id_state: Arc<Mutex<Option<u64>>>,
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl Clock {
+    #[must_use]
+    pub fn system() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn frozen(at: DateTime<Utc>) -> Self {
+        Self {
+            frozen_at: Arc::new(Mutex::new(Some(at))),
+            id_state: Arc::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            frozen_at: Arc::new(Mutex::new(Utc.timestamp_opt(SEEDED_EPOCH_SECS, 0).single())),
+            id_state: Arc::new(Mutex::new(Some(seed))),
+        }
+    }
+
+    #[must_use]
+    pub fn now(&self) -> DateTime<Utc> {
+        self.frozen_at.lock().unwrap().unwrap_or_else(Utc::now)
+    }
+
+    #[must_use]
+    pub fn unix_timestamp(&self) -> u64 {
+        u64::try_from(self.now().timestamp()).unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn next_id(&self) -> Uuid {
+        let mut id_state = self.id_state.lock().unwrap();
+        let Some(state) = id_state.as_mut() else {
+            return Uuid::new_v4();
+        };
+
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&splitmix64(state).to_be_bytes());
+        bytes[8..].copy_from_slice(&splitmix64(state).to_be_bytes());
+        uuid::Builder::from_random_bytes(bytes).into_uuid()
+    }
+
+    #[must_use]
+    pub fn is_seeded(&self) -> bool {
+        self.id_state.lock().unwrap().is_some()
+    }
+
+    #[must_use]
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_at.lock().unwrap().is_some()
+    }
+
+    pub fn pause(&self) {
+        let mut frozen_at = self.frozen_at.lock().unwrap();
+        if frozen_at.is_none() {
+            *frozen_at = Some(Utc::now());
+        }
+    }
+
+    pub fn resume(&self) {
+        *self.frozen_at.lock().unwrap() = None;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let by = chrono::Duration::from_std(by).unwrap_or(chrono::Duration::MAX);
+        let mut frozen_at = self.frozen_at.lock().unwrap();
+        let current = frozen_at.unwrap_or_else(Utc::now);
+        *frozen_at = Some(current.checked_add_signed(by).unwrap_or(current));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frozen_clock_advances_only_on_demand() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 9, 30, 0).unwrap();
+        let clock = Clock::frozen(start);
+        assert_eq!(clock.now(), start);
+        assert_eq!(clock.now(), start);
+
+        let shared = clock.clone();
+        shared.advance(Duration::from_secs(90));
+        assert_eq!(clock.now(), start + chrono::Duration::seconds(90));
+        assert_eq!(clock.unix_timestamp(), 1_705_311_090);
+
+        clock.resume();
+        assert!(!shared.is_frozen());
+        assert!(clock.now() > start);
+
+        clock.pause();
+        let paused = clock.now();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(clock.now(), paused);
+    }
+
+    #[test]
+    fn test_seeded_clock_is_shared_across_threads() {
+        let clock = Clock::seeded(42);
+        let replay = Clock::seeded(42);
+        assert!(clock.is_seeded() && clock.is_frozen());
+        assert_eq!(clock.now(), replay.now());
+
+        let first = clock.next_id();
+        let worker = clock.clone();
+        let second = std::thread::spawn(move || worker.next_id()).join().unwrap();
+
+        assert_eq!(first, replay.next_id());
+        assert_eq!(second, replay.next_id());
+        assert_ne!(first, second);
+        assert_eq!(first.get_version_num(), 4);
+        assert_ne!(Clock::seeded(43).next_id(), first);
+        assert!(!Clock::system().is_seeded());
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use wiremock::{Request, Respond, ResponseTemplate};
+
+#[derive(Debug, Clone, Default)]
+pub struct ResponseDelay(Arc<Mutex<Option<(Duration, Duration)>>>);
+
+impl ResponseDelay {
+    pub fn set(&self, min: Duration, max: Duration) {
+        let range = if min <= max { (min, max) } else { (max, min) };
+        *self.0.lock().unwrap() = Some(range);
+    }
+
+    pub fn clear(&self) {
+        *self.0.lock().unwrap() = None;
+    }
+
+    #[must_use]
+    pub fn range(&self) -> Option<(Duration, Duration)> {
+        *self.0.lock().unwrap()
+    }
+}
+
+fn sample_delay((min, max): (Duration, Duration)) -> Duration {
+    let span = max.saturating_sub(min);
+    if span.is_zero() {
+        return min;
+    }
+    let random = RandomState::new().build_hasher().finish();
+    min + span.mul_f64(random as f64 / u64::MAX as f64)
+}
+
+pub(crate) struct Delayed<R> {
+    delay: ResponseDelay,
+    responder: R,
+}
+
+pub(crate) fn delayed<R: Respond>(delay: &ResponseDelay, responder: R) -> Delayed<R> {
+    Delayed {
+        delay: delay.clone(),
+        responder,
+    }
+}
+
+impl<R: Respond> Respond for Delayed<R> {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let template = self.responder.respond(request);
+        match self.delay.range() {
+            Some(range) => template.set_delay(sample_delay(range)),
+            None => template,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::MockLLM;
+    use crate::ports::PortAllocator;
+    use std::time::Instant;
+
+    #[test]
+    fn test_sample_delay_stays_in_range() {
+        let range = (Duration::from_millis(10), Duration::from_millis(20));
+        for _ in 0..50 {
+            let delay = sample_delay(range);
+            assert!(delay >= range.0 && delay <= range.1);
+        }
+        assert_eq!(sample_delay((range.0, range.0)), range.0);
+    }
+
+    #[tokio::test]
+    async fn test_delayed_response_arrives_after_min() {
+        let llm = MockLLM::start(PortAllocator::allocate()).await.unwrap();
+        let other = MockLLM::start(PortAllocator::allocate()).await.unwrap();
+        let client = reqwest::Client::new();
+
+        llm.response_delay()
+            .set(Duration::from_millis(250), Duration::from_millis(150));
+        assert_eq!(
+            llm.response_delay().range(),
+            Some((Duration::from_millis(150), Duration::from_millis(250)))
+        );
+        assert_eq!(other.response_delay().range(), None);
+
+        let start = Instant::now();
+        let response = client
+            .get(format!("{}/v1/models", llm.url()))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert!(start.elapsed() >= Duration::from_millis(150));
+
+        llm.response_delay().clear();
+        assert_eq!(llm.response_delay().range(), None);
+    }
+}
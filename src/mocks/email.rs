@@ -0,0 +1,352 @@
+use super::delay::{delayed, ResponseDelay};
+use super::{
+    expect_request, new_expectation_store, uncovered_requests, Expectation, ExpectationStore,
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+pub struct MockEmail {
+    server: MockServer,
+    port: u16,
+    expectations: ExpectationStore,
+    delay: ResponseDelay,
+    sent_messages: Arc<Mutex<Vec<SentEmail>>>,
+    received_messages: Arc<Mutex<Vec<InboundEmail>>>,
+    api_key: String,
+    address: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentEmail {
+    pub id: String,
+    pub from: String,
+    pub to: Vec<String>,
+    pub subject: String,
+    pub text: Option<String>,
+    pub html: Option<String>,
+    pub timestamp: u64,
+}
+
+impl SentEmail {
+    #[must_use]
+    pub fn body(&self) -> &str {
+        self.text
+            .as_deref()
+            .or(self.html.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboundEmail {
+    pub message_id: String,
+    pub from: String,
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    errors: Vec<ErrorDetail>,
+}
+
+#[derive(Serialize)]
+struct ErrorDetail {
+    message: String,
+    field: Option<String>,
+}
+
+fn addresses(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get("email").and_then(|e| e.as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn content_of(body: &serde_json::Value, content_type: &str) -> Option<String> {
+    body.get("content")?
+        .as_array()?
+        .iter()
+        .find(|c| c.get("type").and_then(|t| t.as_str()) == Some(content_type))
+        .and_then(|c| c.get("value"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+impl MockEmail {
+    pub const DEFAULT_API_KEY: &'static str = "SG.test_api_key_12345";
+
+    pub const DEFAULT_ADDRESS: &'static str = "bot@test.local";
+
+    pub async fn start(port: u16) -> Result<Self> {
+        let listener = std::net::TcpListener::bind(format!("127.0.0.1:{port}"))
+            .context("Failed to bind MockEmail port")?;
+
+        let server = MockServer::builder().listener(listener).start().await;
+
+        let mock = Self {
+            server,
+            port,
+            expectations: new_expectation_store(),
+            delay: ResponseDelay::default(),
+            sent_messages: Arc::new(Mutex::new(Vec::new())),
+            received_messages: Arc::new(Mutex::new(Vec::new())),
+            api_key: Self::DEFAULT_API_KEY.to_string(),
+            address: Self::DEFAULT_ADDRESS.to_string(),
+        };
+
+        mock.setup_default_routes().await;
+
+        Ok(mock)
+    }
+
+    async fn setup_default_routes(&self) {
+        let sent_messages = self.sent_messages.clone();
+
+        Mock::given(method("POST"))
+            .and(path("/v3/mail/send"))
+            .respond_with(delayed(&self.delay, move |req: &wiremock::Request| {
+                let body: serde_json::Value = req.body_json().unwrap_or_default();
+
+                let to: Vec<String> = body
+                    .get("personalizations")
+                    .and_then(|p| p.as_array())
+                    .map(|items| items.iter().flat_map(|p| addresses(p.get("to"))).collect())
+                    .unwrap_or_default();
+
+                if to.is_empty() {
+                    return ResponseTemplate::new(400).set_body_json(ErrorResponse {
+                        errors: vec![ErrorDetail {
+                            message: "The to array is required for all personalization objects"
+                                .to_string(),
+                            field: Some("personalizations.0.to".to_string()),
+                        }],
+                    });
+                }
+
+                let id = Uuid::new_v4().simple().to_string();
+                let sent = SentEmail {
+                    id: id.clone(),
+                    from: body
+                        .get("from")
+                        .and_then(|f| f.get("email"))
+                        .and_then(|e| e.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    to,
+                    subject: body
+                        .get("subject")
+                        .and_then(|s| s.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    text: content_of(&body, "text/plain"),
+                    html: content_of(&body, "text/html"),
+                    timestamp: std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs(),
+                };
+                sent_messages.lock().unwrap().push(sent);
+
+                ResponseTemplate::new(202).insert_header("X-Message-Id", id.as_str())
+            }))
+            .mount(&self.server)
+            .await;
+    }
+
+    pub fn simulate_incoming(&self, from: &str, subject: &str, text: &str) -> Result<InboundEmail> {
+        let incoming = InboundEmail {
+            message_id: format!("<{}@mail.test.local>", Uuid::new_v4()),
+            from: from.to_string(),
+            to: self.address.clone(),
+            subject: subject.to_string(),
+            text: text.to_string(),
+        };
+
+        self.received_messages
+            .lock()
+            .unwrap()
+            .push(incoming.clone());
+        Ok(incoming)
+    }
+
+    pub async fn expect_error(&self, status: u16, message: &str) {
+        expect_request(
+            &self.expectations,
+            &format!("email error {status}"),
+            "POST",
+            r"^/v3/mail/send$",
+            Some(1),
+        );
+
+        let error_response = ErrorResponse {
+            errors: vec![ErrorDetail {
+                message: message.to_string(),
+                field: None,
+            }],
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/v3/mail/send"))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(status).set_body_json(&error_response),
+            ))
+            .mount(&self.server)
+            .await;
+    }
+
+    #[must_use]
+    pub fn sent_messages(&self) -> Vec<SentEmail> {
+        self.sent_messages.lock().unwrap().clone()
+    }
+
+    #[must_use]
+    pub fn sent_messages_to(&self, address: &str) -> Vec<SentEmail> {
+        self.sent_messages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| m.to.iter().any(|to| to == address))
+            .cloned()
+            .collect()
+    }
+
+    #[must_use]
+    pub fn last_sent_message(&self) -> Option<SentEmail> {
+        self.sent_messages.lock().unwrap().last().cloned()
+    }
+
+    #[must_use]
+    pub fn received_messages(&self) -> Vec<InboundEmail> {
+        self.received_messages.lock().unwrap().clone()
+    }
+
+    pub fn clear_sent_messages(&self) {
+        self.sent_messages.lock().unwrap().clear();
+    }
+
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    #[must_use]
+    pub fn send_url(&self) -> String {
+        format!("{}/v3/mail/send", self.url())
+    }
+
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    #[must_use]
+    pub const fn response_delay(&self) -> &ResponseDelay {
+        &self.delay
+    }
+
+    #[must_use]
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    #[must_use]
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn verify(&self) -> Result<()> {
+        let store = self.expectations.lock().unwrap();
+        for (_, exp) in store.iter() {
+            exp.verify()?;
+        }
+        Ok(())
+    }
+
+    pub async fn reset(&self) {
+        self.server.reset().await;
+        self.sent_messages.lock().unwrap().clear();
+        self.received_messages.lock().unwrap().clear();
+        self.expectations.lock().unwrap().clear();
+        self.setup_default_routes().await;
+    }
+
+    pub async fn received_requests(&self) -> Vec<wiremock::Request> {
+        self.server.received_requests().await.unwrap_or_default()
+    }
+
+    pub fn expect(&self, expectation: Expectation) {
+        self.expectations
+            .lock()
+            .unwrap()
+            .insert(expectation.name.clone(), expectation);
+    }
+
+    pub async fn unexpected_requests(&self) -> Vec<wiremock::Request> {
+        uncovered_requests(self.received_requests().await, &self.expectations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::PortAllocator;
+
+    #[tokio::test]
+    async fn test_inbound_email_and_outbound_reply_captured() {
+        let mock = MockEmail::start(PortAllocator::allocate()).await.unwrap();
+
+        let incoming = mock
+            .simulate_incoming("jane@example.com", "Order status", "Where is order 1234?")
+            .unwrap();
+        assert_eq!(incoming.to, MockEmail::DEFAULT_ADDRESS);
+        assert_eq!(mock.received_messages().len(), 1);
+
+        let response = reqwest::Client::new()
+            .post(mock.send_url())
+            .bearer_auth(mock.api_key())
+            .json(&serde_json::json!({
+                "personalizations": [{"to": [{"email": incoming.from}]}],
+                "from": {"email": incoming.to},
+                "subject": format!("Re: {}", incoming.subject),
+                "content": [{"type": "text/plain", "value": "Order 1234 has shipped."}]
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 202);
+        let message_id = response.headers()["X-Message-Id"]
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let reply = mock.last_sent_message().unwrap();
+        assert_eq!(reply.id, message_id);
+        assert_eq!(reply.to, vec!["jane@example.com"]);
+        assert_eq!(reply.from, MockEmail::DEFAULT_ADDRESS);
+        assert_eq!(reply.subject, "Re: Order status");
+        assert_eq!(reply.body(), "Order 1234 has shipped.");
+        assert_eq!(mock.sent_messages_to("jane@example.com").len(), 1);
+
+        let rejected = reqwest::Client::new()
+            .post(mock.send_url())
+            .json(&serde_json::json!({"personalizations": [], "subject": "x"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(rejected.status().as_u16(), 400);
+        assert_eq!(mock.sent_messages().len(), 1);
+    }
+}
@@ -1,4 +1,7 @@
-use super::{new_expectation_store, Expectation, ExpectationStore};
+use super::delay::{delayed, ResponseDelay};
+use super::{
+    expect_request, new_expectation_store, uncovered_requests, Expectation, ExpectationStore,
+};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fmt::Write;
@@ -8,10 +11,14 @@ use std::time::Duration;
 use wiremock::matchers::{body_partial_json, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
+const COMPLETIONS_PATH: &str = r"^/v1/chat/completions$";
+const EMBEDDINGS_PATH: &str = r"^/v1/embeddings$";
+
 pub struct MockLLM {
     server: MockServer,
     port: u16,
     expectations: ExpectationStore,
+    delay: ResponseDelay,
     completion_responses: Arc<Mutex<Vec<CompletionExpectation>>>,
     embedding_responses: Arc<Mutex<Vec<EmbeddingExpectation>>>,
     default_model: String,
@@ -99,6 +106,29 @@ struct Usage {
     pub total: u32,
 }
 
+fn completion_response(model: &str, content: String) -> ChatCompletionResponse {
+    ChatCompletionResponse {
+        id: format!("chatcmpl-{}", uuid::Uuid::new_v4()),
+        object: "chat.completion".to_string(),
+        created: chrono::Utc::now().timestamp() as u64,
+        model: model.to_string(),
+        choices: vec![ChatChoice {
+            index: 0,
+            message: ChatMessage {
+                role: "assistant".to_string(),
+                content: Some(content),
+                tool_calls: None,
+            },
+            finish_reason: "stop".to_string(),
+        }],
+        usage: Usage {
+            prompt: 10,
+            completion: 20,
+            total: 30,
+        },
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct EmbeddingRequest {
     model: String,
@@ -182,6 +212,7 @@ impl MockLLM {
             server,
             port,
             expectations: new_expectation_store(),
+            delay: ResponseDelay::default(),
             completion_responses: Arc::new(Mutex::new(Vec::new())),
             embedding_responses: Arc::new(Mutex::new(Vec::new())),
             default_model: "gpt-4".to_string(),
@@ -199,14 +230,17 @@ impl MockLLM {
     async fn setup_default_routes(&self) {
         Mock::given(method("GET"))
             .and(path("/v1/models"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-                "object": "list",
-                "data": [
-                    {"id": "gpt-4", "object": "model", "owned_by": "openai"},
-                    {"id": "gpt-3.5-turbo", "object": "model", "owned_by": "openai"},
-                    {"id": "text-embedding-ada-002", "object": "model", "owned_by": "openai"},
-                ]
-            })))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "object": "list",
+                    "data": [
+                        {"id": "gpt-4", "object": "model", "owned_by": "openai"},
+                        {"id": "gpt-3.5-turbo", "object": "model", "owned_by": "openai"},
+                        {"id": "text-embedding-ada-002", "object": "model", "owned_by": "openai"},
+                    ]
+                })),
+            ))
             .mount(&self.server)
             .await;
     }
@@ -225,39 +259,20 @@ impl MockLLM {
             .unwrap()
             .push(expectation.clone());
 
-        {
-            let mut store = self.expectations.lock().unwrap();
-            store.insert(
-                format!("completion:{prompt_contains}"),
-                Expectation::new(&format!("completion containing '{prompt_contains}'")),
-            );
-        }
+        expect_request(
+            &self.expectations,
+            &format!("completion containing '{prompt_contains}'"),
+            "POST",
+            COMPLETIONS_PATH,
+            Some(1),
+        );
 
         let response_text = response.to_string();
         let model = self.default_model.clone();
         let latency = self.latency.clone();
         let call_count = self.call_count.clone();
 
-        let response_body = ChatCompletionResponse {
-            id: format!("chatcmpl-{}", uuid::Uuid::new_v4()),
-            object: "chat.completion".to_string(),
-            created: chrono::Utc::now().timestamp() as u64,
-            model: model.clone(),
-            choices: vec![ChatChoice {
-                index: 0,
-                message: ChatMessage {
-                    role: "assistant".to_string(),
-                    content: Some(response_text),
-                    tool_calls: None,
-                },
-                finish_reason: "stop".to_string(),
-            }],
-            usage: Usage {
-                prompt: 10,
-                completion: 20,
-                total: 30,
-            },
-        };
+        let response_body = completion_response(&model, response_text);
 
         let mut template = ResponseTemplate::new(200).set_body_json(&response_body);
 
@@ -271,7 +286,7 @@ impl MockLLM {
             .and(body_partial_json(serde_json::json!({
                 "messages": [{"content": prompt_contains}]
             })))
-            .respond_with(template)
+            .respond_with(delayed(&self.delay, template))
             .mount(&self.server)
             .await;
 
@@ -279,6 +294,14 @@ impl MockLLM {
     }
 
     pub async fn expect_streaming(&self, prompt_contains: &str, chunks: Vec<&str>) {
+        expect_request(
+            &self.expectations,
+            &format!("streaming completion containing '{prompt_contains}'"),
+            "POST",
+            COMPLETIONS_PATH,
+            Some(1),
+        );
+
         let expectation = CompletionExpectation {
             prompt_contains: Some(prompt_contains.to_string()),
             response: chunks.join(""),
@@ -368,7 +391,7 @@ impl MockLLM {
         Mock::given(method("POST"))
             .and(path("/v1/chat/completions"))
             .and(body_partial_json(serde_json::json!({"stream": true})))
-            .respond_with(template)
+            .respond_with(delayed(&self.delay, template))
             .mount(&self.server)
             .await;
     }
@@ -379,6 +402,14 @@ impl MockLLM {
         tool_name: &str,
         tool_args: serde_json::Value,
     ) {
+        expect_request(
+            &self.expectations,
+            &format!("tool call {tool_name}"),
+            "POST",
+            COMPLETIONS_PATH,
+            Some(1),
+        );
+
         let tool_call = ToolCall {
             id: format!("call_{}", uuid::Uuid::new_v4()),
             r#type: "function".to_string(),
@@ -388,35 +419,31 @@ impl MockLLM {
             },
         };
 
-        let response_body = ChatCompletionResponse {
-            id: format!("chatcmpl-{}", uuid::Uuid::new_v4()),
-            object: "chat.completion".to_string(),
-            created: chrono::Utc::now().timestamp() as u64,
-            model: self.default_model.clone(),
-            choices: vec![ChatChoice {
-                index: 0,
-                message: ChatMessage {
-                    role: "assistant".to_string(),
-                    content: None,
-                    tool_calls: Some(vec![tool_call]),
-                },
-                finish_reason: "tool_calls".to_string(),
-            }],
-            usage: Usage {
-                prompt: 10,
-                completion: 20,
-                total: 30,
-            },
-        };
+        let mut response_body = completion_response(&self.default_model, String::new());
+        let choice = &mut response_body.choices[0];
+        choice.message.content = None;
+        choice.message.tool_calls = Some(vec![tool_call]);
+        choice.finish_reason = "tool_calls".to_string();
 
         Mock::given(method("POST"))
             .and(path("/v1/chat/completions"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&response_body),
+            ))
             .mount(&self.server)
             .await;
     }
 
     pub async fn expect_embedding(&self, dimensions: usize) {
+        expect_request(
+            &self.expectations,
+            "embedding",
+            "POST",
+            EMBEDDINGS_PATH,
+            Some(1),
+        );
+
         let embedding: Vec<f32> = (0..dimensions)
             .map(|i| (i as f32) / (dimensions as f32))
             .collect();
@@ -437,12 +464,23 @@ impl MockLLM {
 
         Mock::given(method("POST"))
             .and(path("/v1/embeddings"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&response_body),
+            ))
             .mount(&self.server)
             .await;
     }
 
     pub async fn expect_embedding_for(&self, input_contains: &str, embedding: Vec<f32>) {
+        expect_request(
+            &self.expectations,
+            &format!("embedding for '{input_contains}'"),
+            "POST",
+            EMBEDDINGS_PATH,
+            Some(1),
+        );
+
         let response_body = EmbeddingResponse {
             object: "list".to_string(),
             data: vec![EmbeddingData {
@@ -462,7 +500,10 @@ impl MockLLM {
             .and(body_partial_json(
                 serde_json::json!({"input": input_contains}),
             ))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&response_body),
+            ))
             .mount(&self.server)
             .await;
     }
@@ -476,6 +517,14 @@ impl MockLLM {
     }
 
     pub async fn next_call_fails(&self, status: u16, message: &str) {
+        expect_request(
+            &self.expectations,
+            &format!("failing completion ({status})"),
+            "POST",
+            COMPLETIONS_PATH,
+            Some(1),
+        );
+
         *self.next_error.lock().unwrap() = Some((status, message.to_string()));
 
         let error_body = ErrorResponse {
@@ -488,13 +537,24 @@ impl MockLLM {
 
         Mock::given(method("POST"))
             .and(path("/v1/chat/completions"))
-            .respond_with(ResponseTemplate::new(status).set_body_json(&error_body))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(status).set_body_json(&error_body),
+            ))
             .expect(1)
             .mount(&self.server)
             .await;
     }
 
     pub async fn expect_rate_limit(&self) {
+        expect_request(
+            &self.expectations,
+            "rate limited completion",
+            "POST",
+            COMPLETIONS_PATH,
+            Some(1),
+        );
+
         let error_body = serde_json::json!({
             "error": {
                 "message": "Rate limit exceeded",
@@ -505,16 +565,25 @@ impl MockLLM {
 
         Mock::given(method("POST"))
             .and(path("/v1/chat/completions"))
-            .respond_with(
+            .respond_with(delayed(
+                &self.delay,
                 ResponseTemplate::new(429)
                     .set_body_json(&error_body)
                     .insert_header("retry-after", "60"),
-            )
+            ))
             .mount(&self.server)
             .await;
     }
 
     pub async fn expect_server_error(&self) {
+        expect_request(
+            &self.expectations,
+            "server error completion",
+            "POST",
+            COMPLETIONS_PATH,
+            Some(1),
+        );
+
         let error_body = serde_json::json!({
             "error": {
                 "message": "Internal server error",
@@ -525,12 +594,23 @@ impl MockLLM {
 
         Mock::given(method("POST"))
             .and(path("/v1/chat/completions"))
-            .respond_with(ResponseTemplate::new(500).set_body_json(&error_body))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(500).set_body_json(&error_body),
+            ))
             .mount(&self.server)
             .await;
     }
 
     pub async fn expect_auth_error(&self) {
+        expect_request(
+            &self.expectations,
+            "unauthorized completion",
+            "POST",
+            COMPLETIONS_PATH,
+            Some(1),
+        );
+
         let error_body = serde_json::json!({
             "error": {
                 "message": "Invalid API key",
@@ -541,36 +621,162 @@ impl MockLLM {
 
         Mock::given(method("POST"))
             .and(path("/v1/chat/completions"))
-            .respond_with(ResponseTemplate::new(401).set_body_json(&error_body))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(401).set_body_json(&error_body),
+            ))
             .mount(&self.server)
             .await;
     }
 
     pub async fn set_default_response(&self, response: &str) {
-        let response_body = ChatCompletionResponse {
-            id: format!("chatcmpl-{}", uuid::Uuid::new_v4()),
-            object: "chat.completion".to_string(),
-            created: chrono::Utc::now().timestamp() as u64,
-            model: self.default_model.clone(),
-            choices: vec![ChatChoice {
-                index: 0,
-                message: ChatMessage {
-                    role: "assistant".to_string(),
-                    content: Some(response.to_string()),
-                    tool_calls: None,
+        expect_request(
+            &self.expectations,
+            "default completion",
+            "POST",
+            COMPLETIONS_PATH,
+            None,
+        );
+
+        let response_body = completion_response(&self.default_model, response.to_string());
+
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&response_body),
+            ))
+            .mount(&self.server)
+            .await;
+    }
+
+    pub async fn expect_refusal(&self, reason: &str) {
+        expect_request(
+            &self.expectations,
+            "refused completion",
+            "POST",
+            COMPLETIONS_PATH,
+            Some(1),
+        );
+
+        let response_body = serde_json::json!({
+            "id": format!("chatcmpl-{}", uuid::Uuid::new_v4()),
+            "object": "chat.completion",
+            "created": chrono::Utc::now().timestamp(),
+            "model": self.default_model,
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "refusal": reason
                 },
-                finish_reason: "stop".to_string(),
+                "finish_reason": "content_filter"
             }],
-            usage: Usage {
-                prompt: 10,
-                completion: 20,
-                total: 30,
-            },
-        };
+            "usage": {
+                "prompt_tokens": 10,
+                "completion_tokens": 0,
+                "total_tokens": 10
+            }
+        });
+
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&response_body),
+            ))
+            .mount(&self.server)
+            .await;
+    }
+
+    pub async fn expect_json(&self, value: serde_json::Value) {
+        let key = format!("json:{value}");
+        self.expectations.lock().unwrap().insert(
+            key.clone(),
+            Expectation::new("completion in JSON mode")
+                .for_request("POST", COMPLETIONS_PATH)
+                .unwrap()
+                .at_most(1)
+                .with_body_matching("/response_format/type", serde_json::json!("json_object")),
+        );
+
+        let expectations = self.expectations.clone();
+        let model = self.default_model.clone();
+        let content = value.to_string();
+
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(delayed(&self.delay, move |req: &wiremock::Request| {
+                let json_mode = {
+                    let mut store = expectations.lock().unwrap();
+                    store.get_mut(&key).is_some_and(|exp| {
+                        let mismatches = exp.body_mismatches.len();
+                        exp.record_request(&req.body);
+                        exp.body_mismatches.len() == mismatches
+                    })
+                };
+                if !json_mode {
+                    return ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                        "error": {
+                            "message": "Expected response_format {\"type\": \"json_object\"}",
+                            "type": "invalid_request_error",
+                            "code": "json_mode_required"
+                        }
+                    }));
+                }
+
+                ResponseTemplate::new(200)
+                    .set_body_json(completion_response(&model, content.clone()))
+            }))
+            .mount(&self.server)
+            .await;
+    }
+
+    pub async fn echo_mode(&self) {
+        self.echo_mode_with_prefix("").await;
+    }
+
+    pub async fn echo_mode_with_prefix(&self, prefix: &str) {
+        expect_request(
+            &self.expectations,
+            "echoed completion",
+            "POST",
+            COMPLETIONS_PATH,
+            None,
+        );
+
+        let prefix = prefix.to_string();
+        let model = self.default_model.clone();
 
         Mock::given(method("POST"))
             .and(path("/v1/chat/completions"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .respond_with(delayed(&self.delay, move |req: &wiremock::Request| {
+                let Ok(request) = serde_json::from_slice::<ChatCompletionRequest>(&req.body) else {
+                    return ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                        "error": {
+                            "message": "Invalid chat completion request",
+                            "type": "invalid_request_error",
+                            "code": "invalid_request"
+                        }
+                    }));
+                };
+                let last_user = request
+                    .messages
+                    .iter()
+                    .rev()
+                    .find(|m| m.role == "user")
+                    .and_then(|m| m.content.clone())
+                    .unwrap_or_default();
+
+                let model = if request.model.is_empty() {
+                    &model
+                } else {
+                    &request.model
+                };
+                ResponseTemplate::new(200)
+                    .set_body_json(completion_response(model, format!("{prefix}{last_user}")))
+            }))
             .mount(&self.server)
             .await;
     }
@@ -585,6 +791,11 @@ impl MockLLM {
         self.port
     }
 
+    #[must_use]
+    pub const fn response_delay(&self) -> &ResponseDelay {
+        &self.delay
+    }
+
     pub fn verify(&self) -> Result<()> {
         let store = self.expectations.lock().unwrap();
         for (_, exp) in store.iter() {
@@ -607,6 +818,17 @@ impl MockLLM {
         self.server.received_requests().await.unwrap_or_default()
     }
 
+    pub fn expect(&self, expectation: Expectation) {
+        self.expectations
+            .lock()
+            .unwrap()
+            .insert(expectation.name.clone(), expectation);
+    }
+
+    pub async fn unexpected_requests(&self) -> Vec<wiremock::Request> {
+        uncovered_requests(self.received_requests().await, &self.expectations)
+    }
+
     pub async fn call_count(&self) -> usize {
         self.server.received_requests().await.map_or(0, |r| r.len())
     }
@@ -637,6 +859,146 @@ impl MockLLM {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_echo_mode_returns_last_user_message() {
+        let llm = MockLLM::start(crate::ports::PortAllocator::allocate())
+            .await
+            .unwrap();
+        llm.echo_mode().await;
+        let client = reqwest::Client::new();
+
+        let body: serde_json::Value = client
+            .post(format!("{}/v1/chat/completions", llm.url()))
+            .json(&serde_json::json!({
+                "model": "gpt-4",
+                "messages": [
+                    {"role": "system", "content": "You are a helpful assistant."},
+                    {"role": "user", "content": "first"},
+                    {"role": "assistant", "content": "ok"},
+                    {"role": "user", "content": "ping from the bot"}
+                ]
+            }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(
+            body["choices"][0]["message"]["content"],
+            "ping from the bot"
+        );
+        assert_eq!(body["model"], "gpt-4");
+
+        llm.reset().await;
+        llm.echo_mode_with_prefix("Echo: ").await;
+        let body: serde_json::Value = client
+            .post(format!("{}/v1/chat/completions", llm.url()))
+            .json(&serde_json::json!({
+                "model": "gpt-4",
+                "messages": [{"role": "user", "content": "hello"}]
+            }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["choices"][0]["message"]["content"], "Echo: hello");
+    }
+
+    #[tokio::test]
+    async fn test_refusal_triggers_fallback_branch() {
+        fn handle(completion: &serde_json::Value) -> String {
+            let choice = &completion["choices"][0];
+            match choice["finish_reason"].as_str() {
+                Some("content_filter") => "TRANSFER_TO_HUMAN".to_string(),
+                _ => choice["message"]["content"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+            }
+        }
+
+        let llm = MockLLM::start(crate::ports::PortAllocator::allocate())
+            .await
+            .unwrap();
+        let client = reqwest::Client::new();
+        let request = serde_json::json!({
+            "model": "gpt-4",
+            "messages": [{"role": "user", "content": "something disallowed"}]
+        });
+
+        llm.set_default_response("Sure, here you go").await;
+        let allowed: serde_json::Value = client
+            .post(format!("{}/v1/chat/completions", llm.url()))
+            .json(&request)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(handle(&allowed), "Sure, here you go");
+
+        llm.reset().await;
+        llm.expect_refusal("violence").await;
+        let refused: serde_json::Value = client
+            .post(format!("{}/v1/chat/completions", llm.url()))
+            .json(&request)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(refused["choices"][0]["finish_reason"], "content_filter");
+        assert!(refused["choices"][0]["message"]["content"].is_null());
+        assert_eq!(refused["choices"][0]["message"]["refusal"], "violence");
+        assert_eq!(handle(&refused), "TRANSFER_TO_HUMAN");
+    }
+
+    #[tokio::test]
+    async fn test_expect_json_requires_json_mode() {
+        let llm = MockLLM::start(crate::ports::PortAllocator::allocate())
+            .await
+            .unwrap();
+        let expected = serde_json::json!({"intent": "order_status", "order_id": 42});
+        llm.expect_json(expected.clone()).await;
+        let client = reqwest::Client::new();
+
+        let body: serde_json::Value = client
+            .post(format!("{}/v1/chat/completions", llm.url()))
+            .json(&serde_json::json!({
+                "model": "gpt-4",
+                "response_format": {"type": "json_object"},
+                "messages": [{"role": "user", "content": "Where is order 42?"}]
+            }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let content = body["choices"][0]["message"]["content"].as_str().unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(content).unwrap();
+        assert_eq!(parsed, expected);
+        assert!(llm.verify().is_ok());
+
+        let plain = client
+            .post(format!("{}/v1/chat/completions", llm.url()))
+            .json(&serde_json::json!({
+                "model": "gpt-4",
+                "messages": [{"role": "user", "content": "Where is order 42?"}]
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(plain.status().as_u16(), 400);
+        let err = llm.verify().unwrap_err().to_string();
+        assert!(err.contains("/response_format/type missing"));
+    }
+
     #[test]
     fn test_tool_call_serialization() {
         let tool_call = ToolCall {
@@ -1,23 +1,39 @@
 
+mod assertions;
+mod botserver;
+mod clock;
+mod delay;
+mod email;
 mod llm;
+mod sms;
 mod teams;
 mod whatsapp;
 mod zitadel;
 
+pub use assertions::RequestAssertions;
+pub use botserver::{ChatTurn, StubBot, StubBotServer};
+pub use clock::Clock;
+pub use delay::ResponseDelay;
+pub use email::MockEmail;
 pub use llm::MockLLM;
+pub use sms::MockSms;
 pub use teams::MockTeams;
-pub use whatsapp::MockWhatsApp;
+pub use whatsapp::{MockWhatsApp, VerificationRequest};
 pub use zitadel::MockZitadel;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use regex::Regex;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 pub struct MockRegistry {
     pub llm: Option<MockLLM>,
     pub whatsapp: Option<MockWhatsApp>,
     pub teams: Option<MockTeams>,
     pub zitadel: Option<MockZitadel>,
+    pub sms: Option<MockSms>,
+    pub email: Option<MockEmail>,
 }
 
 impl MockRegistry {
@@ -28,6 +44,8 @@ impl MockRegistry {
             whatsapp: None,
             teams: None,
             zitadel: None,
+            sms: None,
+            email: None,
         }
     }
 
@@ -51,6 +69,30 @@ impl MockRegistry {
         self.zitadel.as_ref().expect("Zitadel mock not configured")
     }
 
+    #[must_use]
+    pub const fn sms(&self) -> &MockSms {
+        self.sms.as_ref().expect("SMS mock not configured")
+    }
+
+    #[must_use]
+    pub const fn email(&self) -> &MockEmail {
+        self.email.as_ref().expect("Email mock not configured")
+    }
+
+    pub fn with_response_delay(&self, min: Duration, max: Duration) {
+        let delays = [
+            self.llm.as_ref().map(MockLLM::response_delay),
+            self.whatsapp.as_ref().map(MockWhatsApp::response_delay),
+            self.teams.as_ref().map(MockTeams::response_delay),
+            self.zitadel.as_ref().map(MockZitadel::response_delay),
+            self.sms.as_ref().map(MockSms::response_delay),
+            self.email.as_ref().map(MockEmail::response_delay),
+        ];
+        for delay in delays.into_iter().flatten() {
+            delay.set(min, max);
+        }
+    }
+
     pub fn verify_all(&self) -> Result<()> {
         if let Some(ref llm) = self.llm {
             llm.verify()?;
@@ -64,6 +106,52 @@ impl MockRegistry {
         if let Some(ref zitadel) = self.zitadel {
             zitadel.verify()?;
         }
+        if let Some(ref sms) = self.sms {
+            sms.verify()?;
+        }
+        if let Some(ref email) = self.email {
+            email.verify()?;
+        }
+        Ok(())
+    }
+
+    pub async fn verify_strict(&self) -> Result<()> {
+        self.verify_all()?;
+
+        let mut unexpected = Vec::new();
+        if let Some(ref llm) = self.llm {
+            unexpected.push(("LLM", llm.unexpected_requests().await));
+        }
+        if let Some(ref whatsapp) = self.whatsapp {
+            unexpected.push(("WhatsApp", whatsapp.unexpected_requests().await));
+        }
+        if let Some(ref teams) = self.teams {
+            unexpected.push(("Teams", teams.unexpected_requests().await));
+        }
+        if let Some(ref zitadel) = self.zitadel {
+            unexpected.push(("Zitadel", zitadel.unexpected_requests().await));
+        }
+        if let Some(ref sms) = self.sms {
+            unexpected.push(("SMS", sms.unexpected_requests().await));
+        }
+        if let Some(ref email) = self.email {
+            unexpected.push(("Email", email.unexpected_requests().await));
+        }
+
+        let report: Vec<String> = unexpected
+            .into_iter()
+            .filter(|(_, requests)| !requests.is_empty())
+            .map(|(name, requests)| {
+                let calls: Vec<String> = requests
+                    .iter()
+                    .map(|req| format!("{} {}", req.method, req.url.path()))
+                    .collect();
+                format!("{name}: {}", calls.join(", "))
+            })
+            .collect();
+        if !report.is_empty() {
+            anyhow::bail!("Unexpected mock calls: {}", report.join("; "));
+        }
         Ok(())
     }
 
@@ -80,6 +168,12 @@ impl MockRegistry {
         if let Some(ref zitadel) = self.zitadel {
             zitadel.reset().await;
         }
+        if let Some(ref sms) = self.sms {
+            sms.reset().await;
+        }
+        if let Some(ref email) = self.email {
+            email.reset().await;
+        }
     }
 }
 
@@ -95,6 +189,10 @@ pub struct Expectation {
     pub expected_calls: Option<usize>,
     pub actual_calls: usize,
     pub matched: bool,
+    pub body_matchers: Vec<(String, serde_json::Value)>,
+    pub body_mismatches: Vec<String>,
+    pub request: Option<(String, Regex)>,
+    pub max_calls: Option<usize>,
 }
 
 impl Expectation {
@@ -105,20 +203,85 @@ impl Expectation {
             expected_calls: None,
             actual_calls: 0,
             matched: false,
+            body_matchers: Vec::new(),
+            body_mismatches: Vec::new(),
+            request: None,
+            max_calls: None,
         }
     }
 
+    pub fn for_request(mut self, method: &str, path_regex: &str) -> Result<Self> {
+        let pattern =
+            Regex::new(path_regex).with_context(|| format!("Invalid path regex: {path_regex}"))?;
+        self.request = Some((method.to_string(), pattern));
+        Ok(self)
+    }
+
+    #[must_use]
+    pub fn covers(&self, request: &wiremock::Request) -> bool {
+        self.request.as_ref().is_some_and(|(method, pattern)| {
+            request.method.to_string().eq_ignore_ascii_case(method)
+                && pattern.is_match(request.url.path())
+        })
+    }
+
     #[must_use]
     pub const fn times(mut self, n: usize) -> Self {
         self.expected_calls = Some(n);
         self
     }
 
+    #[must_use]
+    pub const fn at_most(mut self, n: usize) -> Self {
+        self.max_calls = Some(n);
+        self
+    }
+
+    #[must_use]
+    pub const fn call_budget(&self) -> Option<usize> {
+        match self.expected_calls {
+            Some(n) => Some(n),
+            None => self.max_calls,
+        }
+    }
+
+    #[must_use]
+    pub fn with_body_matching(mut self, json_pointer: &str, expected: serde_json::Value) -> Self {
+        self.body_matchers
+            .push((json_pointer.to_string(), expected));
+        self
+    }
+
     pub const fn record_call(&mut self) {
         self.actual_calls += 1;
         self.matched = true;
     }
 
+    pub fn record_request(&mut self, body: &[u8]) {
+        self.record_call();
+        if self.body_matchers.is_empty() {
+            return;
+        }
+
+        let call = self.actual_calls;
+        let Ok(json) = serde_json::from_slice::<serde_json::Value>(body) else {
+            self.body_mismatches
+                .push(format!("call {call}: body is not valid JSON"));
+            return;
+        };
+        for (pointer, expected) in &self.body_matchers {
+            match json.pointer(pointer) {
+                Some(actual) if actual == expected => {}
+                Some(actual) => self.body_mismatches.push(format!(
+                    "call {call}: {pointer} was {actual}, expected {expected}"
+                )),
+                None => self.body_mismatches.push(format!(
+                    "call {call}: {pointer} missing, expected {expected}"
+                )),
+            }
+        }
+    }
+
     pub fn verify(&self) -> Result<()> {
         if let Some(expected) = self.expected_calls {
             if self.actual_calls != expected {
@@ -130,6 +293,13 @@ impl Expectation {
                 );
             }
         }
+        if !self.body_mismatches.is_empty() {
+            anyhow::bail!(
+                "Expectation '{}' body mismatch: {}",
+                self.name,
+                self.body_mismatches.join("; ")
+            );
+        }
         Ok(())
     }
 }
@@ -141,6 +311,51 @@ pub fn new_expectation_store() -> ExpectationStore {
     Arc::new(Mutex::new(HashMap::new()))
 }
 
+pub fn expect_request(
+    store: &ExpectationStore,
+    name: &str,
+    method: &str,
+    path_regex: &str,
+    max_calls: Option<usize>,
+) {
+    let mut expectation = Expectation::new(name)
+        .for_request(method, path_regex)
+        .unwrap();
+    expectation.max_calls = max_calls;
+    let mut store = store.lock().unwrap();
+    let key = format!("{name}#{}", store.len());
+    store.insert(key, expectation);
+}
+
+#[must_use]
+pub fn uncovered_requests(
+    requests: Vec<wiremock::Request>,
+    store: &ExpectationStore,
+) -> Vec<wiremock::Request> {
+    let store = store.lock().unwrap();
+    let mut keys: Vec<&String> = store.keys().collect();
+    keys.sort_by_key(|key| (store[*key].call_budget().is_none(), *key));
+
+    let mut used: HashMap<&String, usize> = HashMap::new();
+    requests
+        .into_iter()
+        .filter(|req| {
+            let slot = keys.iter().copied().find(|key| {
+                let exp = &store[*key];
+                exp.covers(req)
+                    && exp
+                        .call_budget()
+                        .is_none_or(|budget| used.get(*key).copied().unwrap_or(0) < budget)
+            });
+            let Some(key) = slot else {
+                return true;
+            };
+            *used.entry(key).or_default() += 1;
+            false
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +388,155 @@ mod tests {
         assert!(exp.verify().is_err());
     }
 
+    #[test]
+    fn test_expectation_body_matching() {
+        let mut exp = Expectation::new("completion")
+            .with_body_matching("/temperature", serde_json::json!(0.2))
+            .with_body_matching("/messages/0/role", serde_json::json!("system"));
+
+        exp.record_request(
+            br#"{"model":"gpt-4","temperature":0.2,"messages":[{"role":"system","content":"hi"}]}"#,
+        );
+
+        assert_eq!(exp.actual_calls, 1);
+        assert!(exp.verify().is_ok());
+    }
+
+    #[test]
+    fn test_expectation_body_mismatch_fails() {
+        let mut exp = Expectation::new("completion")
+            .times(2)
+            .with_body_matching("/temperature", serde_json::json!(0.2));
+
+        exp.record_request(br#"{"temperature":0.2}"#);
+        exp.record_request(br#"{"temperature":0.9}"#);
+
+        let err = exp.verify().unwrap_err().to_string();
+        assert!(err.contains("call 2: /temperature was 0.9, expected 0.2"));
+
+        let mut missing = Expectation::new("completion")
+            .with_body_matching("/temperature", serde_json::json!(0.2));
+        missing.record_request(b"{}");
+        assert!(missing.verify().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_strict_rejects_undeclared_calls() {
+        let llm = MockLLM::start(crate::ports::PortAllocator::allocate())
+            .await
+            .unwrap();
+        llm.expect_completion("hello", "Hi there!").await;
+        let client = reqwest::Client::new();
+        client
+            .post(format!("{}/v1/chat/completions", llm.url()))
+            .json(&serde_json::json!({
+                "model": "gpt-4",
+                "messages": [{"role": "user", "content": "hello"}]
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        let mut registry = MockRegistry::new();
+        registry.llm = Some(llm);
+        assert!(registry.verify_all().is_ok());
+        assert!(registry.verify_strict().await.is_ok());
+
+        client
+            .get(format!("{}/v1/models", registry.llm().url()))
+            .send()
+            .await
+            .unwrap();
+        assert!(registry.verify_all().is_ok());
+        let err = registry.verify_strict().await.unwrap_err().to_string();
+        assert!(err.contains("LLM: GET /v1/models"));
+        assert!(!err.contains("/v1/chat/completions"));
+
+        registry.llm().expect(
+            Expectation::new("models")
+                .for_request("GET", "^/v1/models$")
+                .unwrap(),
+        );
+        assert!(registry.verify_strict().await.is_ok());
+
+        client
+            .post(format!("{}/v1/chat/completions", registry.llm().url()))
+            .json(&serde_json::json!({
+                "model": "gpt-4",
+                "messages": [{"role": "user", "content": "hello"}]
+            }))
+            .send()
+            .await
+            .unwrap();
+        let err = registry.verify_strict().await.unwrap_err().to_string();
+        assert!(err.contains("LLM: POST /v1/chat/completions"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_strict_accepts_declared_channel_calls() {
+        async fn send(whatsapp: &MockWhatsApp) {
+            reqwest::Client::new()
+                .post(format!(
+                    "{}/{}/messages",
+                    whatsapp.graph_api_url(),
+                    whatsapp.phone_number_id()
+                ))
+                .json(&serde_json::json!({
+                    "messaging_product": "whatsapp",
+                    "to": "+15551234567",
+                    "type": "text",
+                    "text": {"body": "Hi"}
+                }))
+                .send()
+                .await
+                .unwrap();
+        }
+
+        let whatsapp = MockWhatsApp::start(crate::ports::PortAllocator::allocate())
+            .await
+            .unwrap();
+        let _ = whatsapp.expect_send_message("+15551234567");
+        send(&whatsapp).await;
+
+        let mut registry = MockRegistry::new();
+        registry.whatsapp = Some(whatsapp);
+        assert!(registry.verify_strict().await.is_ok());
+
+        send(registry.whatsapp()).await;
+        let err = registry.verify_strict().await.unwrap_err().to_string();
+        assert!(err.contains("WhatsApp: POST"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_strict_accepts_declared_login() {
+        async fn login(zitadel: &MockZitadel) {
+            reqwest::Client::new()
+                .post(format!("{}/oauth/v2/token", zitadel.url()))
+                .form(&[
+                    ("grant_type", "password"),
+                    ("username", "user@example.com"),
+                    ("password", "secret"),
+                ])
+                .send()
+                .await
+                .unwrap();
+        }
+
+        let zitadel = MockZitadel::start(crate::ports::PortAllocator::allocate())
+            .await
+            .unwrap();
+        zitadel.expect_login("user@example.com", "secret").await;
+        login(&zitadel).await;
+
+        let mut registry = MockRegistry::new();
+        registry.zitadel = Some(zitadel);
+        assert!(registry.verify_strict().await.is_ok());
+
+        login(registry.zitadel()).await;
+        let err = registry.verify_strict().await.unwrap_err().to_string();
+        assert!(err.contains("Zitadel: POST /oauth/v2/token"));
+    }
+
     #[test]
     fn test_mock_registry_default() {
         let registry = MockRegistry::new();
@@ -180,5 +544,7 @@ mod tests {
         assert!(registry.whatsapp.is_none());
         assert!(registry.teams.is_none());
         assert!(registry.zitadel.is_none());
+        assert!(registry.sms.is_none());
+        assert!(registry.email.is_none());
     }
 }
@@ -0,0 +1,406 @@
+use super::delay::{delayed, ResponseDelay};
+use super::{
+    expect_request, new_expectation_store, uncovered_requests, Expectation, ExpectationStore,
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+pub struct MockSms {
+    server: MockServer,
+    port: u16,
+    expectations: ExpectationStore,
+    delay: ResponseDelay,
+    sent_messages: Arc<Mutex<Vec<SentSms>>>,
+    received_messages: Arc<Mutex<Vec<IncomingSms>>>,
+    account_sid: String,
+    auth_token: String,
+    phone_number: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentSms {
+    pub sid: String,
+    pub to: String,
+    pub from: String,
+    pub body: String,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct IncomingSms {
+    pub message_sid: String,
+    pub account_sid: String,
+    pub from: String,
+    pub to: String,
+    pub body: String,
+    pub num_media: String,
+}
+
+impl IncomingSms {
+    #[must_use]
+    pub fn form_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("MessageSid", self.message_sid.clone()),
+            ("SmsSid", self.message_sid.clone()),
+            ("AccountSid", self.account_sid.clone()),
+            ("From", self.from.clone()),
+            ("To", self.to.clone()),
+            ("Body", self.body.clone()),
+            ("NumMedia", self.num_media.clone()),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+struct MessageResource {
+    sid: String,
+    account_sid: String,
+    to: String,
+    from: String,
+    body: String,
+    status: String,
+    num_segments: String,
+    direction: String,
+    date_created: String,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    code: u32,
+    message: String,
+    more_info: String,
+    status: u16,
+}
+
+fn decode_form(body: &[u8]) -> HashMap<String, String> {
+    fn decode(component: &str) -> String {
+        let bytes = component.as_bytes();
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let escaped = bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            match (bytes[i], escaped) {
+                (b'+', _) => out.push(b' '),
+                (b'%', Some(byte)) => {
+                    out.push(byte);
+                    i += 2;
+                }
+                (byte, _) => out.push(byte),
+            }
+            i += 1;
+        }
+        String::from_utf8_lossy(&out).into_owned()
+    }
+
+    String::from_utf8_lossy(body)
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key), decode(value))
+        })
+        .collect()
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn new_sid(prefix: &str) -> String {
+    format!("{prefix}{}", Uuid::new_v4().simple())
+}
+
+impl MockSms {
+    pub const DEFAULT_ACCOUNT_SID: &'static str = "AC00000000000000000000000000000000";
+
+    pub const DEFAULT_AUTH_TOKEN: &'static str = "test_auth_token_12345";
+
+    pub const DEFAULT_PHONE_NUMBER: &'static str = "+15550001000";
+
+    pub async fn start(port: u16) -> Result<Self> {
+        let listener = std::net::TcpListener::bind(format!("127.0.0.1:{port}"))
+            .context("Failed to bind MockSms port")?;
+
+        let server = MockServer::builder().listener(listener).start().await;
+
+        let mock = Self {
+            server,
+            port,
+            expectations: new_expectation_store(),
+            delay: ResponseDelay::default(),
+            sent_messages: Arc::new(Mutex::new(Vec::new())),
+            received_messages: Arc::new(Mutex::new(Vec::new())),
+            account_sid: Self::DEFAULT_ACCOUNT_SID.to_string(),
+            auth_token: Self::DEFAULT_AUTH_TOKEN.to_string(),
+            phone_number: Self::DEFAULT_PHONE_NUMBER.to_string(),
+        };
+
+        mock.setup_default_routes().await;
+
+        Ok(mock)
+    }
+
+    async fn setup_default_routes(&self) {
+        let sent_messages = self.sent_messages.clone();
+        let default_from = self.phone_number.clone();
+
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/2010-04-01/Accounts/[^/]+/Messages\.json$"))
+            .respond_with(delayed(&self.delay, move |req: &wiremock::Request| {
+                let form = decode_form(&req.body);
+                let account_sid = req
+                    .url
+                    .path_segments()
+                    .and_then(|mut segments| segments.nth(2))
+                    .unwrap_or_default()
+                    .to_string();
+
+                let Some(to) = form.get("To").cloned() else {
+                    return ResponseTemplate::new(400).set_body_json(ErrorResponse {
+                        code: 21604,
+                        message: "A 'To' phone number is required.".to_string(),
+                        more_info: "https://www.twilio.com/docs/errors/21604".to_string(),
+                        status: 400,
+                    });
+                };
+
+                let sent = SentSms {
+                    sid: new_sid("SM"),
+                    to,
+                    from: form
+                        .get("From")
+                        .cloned()
+                        .unwrap_or_else(|| default_from.clone()),
+                    body: form.get("Body").cloned().unwrap_or_default(),
+                    timestamp: unix_now(),
+                };
+                sent_messages.lock().unwrap().push(sent.clone());
+
+                ResponseTemplate::new(201).set_body_json(MessageResource {
+                    sid: sent.sid,
+                    account_sid,
+                    to: sent.to,
+                    from: sent.from,
+                    num_segments: sent.body.len().div_ceil(160).max(1).to_string(),
+                    body: sent.body,
+                    status: "queued".to_string(),
+                    direction: "outbound-api".to_string(),
+                    date_created: chrono::Utc::now().to_rfc2822(),
+                })
+            }))
+            .mount(&self.server)
+            .await;
+    }
+
+    pub fn simulate_incoming(&self, from: &str, body: &str) -> Result<IncomingSms> {
+        let incoming = IncomingSms {
+            message_sid: new_sid("SM"),
+            account_sid: self.account_sid.clone(),
+            from: from.to_string(),
+            to: self.phone_number.clone(),
+            body: body.to_string(),
+            num_media: "0".to_string(),
+        };
+
+        self.received_messages
+            .lock()
+            .unwrap()
+            .push(incoming.clone());
+        Ok(incoming)
+    }
+
+    pub async fn expect_error(&self, status: u16, code: u32, message: &str) {
+        expect_request(
+            &self.expectations,
+            &format!("SMS error {code}"),
+            "POST",
+            r"^/2010-04-01/Accounts/[^/]+/Messages\.json$",
+            Some(1),
+        );
+
+        let error_response = ErrorResponse {
+            code,
+            message: message.to_string(),
+            more_info: format!("https://www.twilio.com/docs/errors/{code}"),
+            status,
+        };
+
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/2010-04-01/Accounts/[^/]+/Messages\.json$"))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(status).set_body_json(&error_response),
+            ))
+            .mount(&self.server)
+            .await;
+    }
+
+    #[must_use]
+    pub fn sent_messages(&self) -> Vec<SentSms> {
+        self.sent_messages.lock().unwrap().clone()
+    }
+
+    #[must_use]
+    pub fn sent_messages_to(&self, phone: &str) -> Vec<SentSms> {
+        self.sent_messages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| m.to == phone)
+            .cloned()
+            .collect()
+    }
+
+    #[must_use]
+    pub fn last_sent_message(&self) -> Option<SentSms> {
+        self.sent_messages.lock().unwrap().last().cloned()
+    }
+
+    #[must_use]
+    pub fn received_messages(&self) -> Vec<IncomingSms> {
+        self.received_messages.lock().unwrap().clone()
+    }
+
+    pub fn clear_sent_messages(&self) {
+        self.sent_messages.lock().unwrap().clear();
+    }
+
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    #[must_use]
+    pub fn messages_url(&self) -> String {
+        format!(
+            "{}/2010-04-01/Accounts/{}/Messages.json",
+            self.url(),
+            self.account_sid
+        )
+    }
+
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    #[must_use]
+    pub const fn response_delay(&self) -> &ResponseDelay {
+        &self.delay
+    }
+
+    #[must_use]
+    pub fn account_sid(&self) -> &str {
+        &self.account_sid
+    }
+
+    #[must_use]
+    pub fn auth_token(&self) -> &str {
+        &self.auth_token
+    }
+
+    #[must_use]
+    pub fn phone_number(&self) -> &str {
+        &self.phone_number
+    }
+
+    pub fn verify(&self) -> Result<()> {
+        let store = self.expectations.lock().unwrap();
+        for (_, exp) in store.iter() {
+            exp.verify()?;
+        }
+        Ok(())
+    }
+
+    pub async fn reset(&self) {
+        self.server.reset().await;
+        self.sent_messages.lock().unwrap().clear();
+        self.received_messages.lock().unwrap().clear();
+        self.expectations.lock().unwrap().clear();
+        self.setup_default_routes().await;
+    }
+
+    pub async fn received_requests(&self) -> Vec<wiremock::Request> {
+        self.server.received_requests().await.unwrap_or_default()
+    }
+
+    pub fn expect(&self, expectation: Expectation) {
+        self.expectations
+            .lock()
+            .unwrap()
+            .insert(expectation.name.clone(), expectation);
+    }
+
+    pub async fn unexpected_requests(&self) -> Vec<wiremock::Request> {
+        uncovered_requests(self.received_requests().await, &self.expectations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::PortAllocator;
+
+    #[test]
+    fn test_decode_form() {
+        let form = decode_form(b"To=%2B15551234567&Body=Hello+there%21&Empty=&Bad=%zz");
+        assert_eq!(form["To"], "+15551234567");
+        assert_eq!(form["Body"], "Hello there!");
+        assert_eq!(form["Empty"], "");
+        assert_eq!(form["Bad"], "%zz");
+    }
+
+    #[tokio::test]
+    async fn test_inbound_sms_and_outbound_reply_captured() {
+        let mock = MockSms::start(PortAllocator::allocate()).await.unwrap();
+
+        let incoming = mock
+            .simulate_incoming("+15557654321", "STATUS 1234")
+            .unwrap();
+        assert_eq!(incoming.to, MockSms::DEFAULT_PHONE_NUMBER);
+        assert!(incoming.message_sid.starts_with("SM"));
+        assert!(incoming
+            .form_fields()
+            .contains(&("Body", "STATUS 1234".to_string())));
+        assert_eq!(mock.received_messages().len(), 1);
+
+        let response = reqwest::Client::new()
+            .post(mock.messages_url())
+            .basic_auth(mock.account_sid(), Some(mock.auth_token()))
+            .form(&[
+                ("To", incoming.from.as_str()),
+                ("From", incoming.to.as_str()),
+                ("Body", "Order 1234 has shipped & is on its way"),
+            ])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 201);
+
+        let resource: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(resource["status"], "queued");
+        assert_eq!(resource["account_sid"], MockSms::DEFAULT_ACCOUNT_SID);
+
+        let replies = mock.sent_messages_to("+15557654321");
+        assert_eq!(replies.len(), 1);
+        assert_eq!(replies[0].body, "Order 1234 has shipped & is on its way");
+        assert_eq!(replies[0].from, MockSms::DEFAULT_PHONE_NUMBER);
+        assert_eq!(resource["sid"], replies[0].sid.as_str());
+
+        mock.reset().await;
+        assert!(mock.sent_messages().is_empty());
+        assert!(mock.received_messages().is_empty());
+    }
+}
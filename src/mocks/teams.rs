@@ -1,4 +1,7 @@
-use super::{new_expectation_store, ExpectationStore};
+use super::delay::{delayed, ResponseDelay};
+use super::{
+    expect_request, new_expectation_store, uncovered_requests, Clock, Expectation, ExpectationStore,
+};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -11,6 +14,8 @@ pub struct MockTeams {
     server: MockServer,
     port: u16,
     expectations: ExpectationStore,
+    delay: ResponseDelay,
+    clock: Clock,
     sent_activities: Arc<Mutex<Vec<Activity>>>,
     conversations: Arc<Mutex<HashMap<String, ConversationInfo>>>,
     bot_id: String,
@@ -53,6 +58,8 @@ pub struct Activity {
     pub value: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members_added: Option<Vec<ChannelAccount>>,
 }
 
 impl Default for Activity {
@@ -77,6 +84,7 @@ impl Default for Activity {
             reply_to_id: None,
             value: None,
             name: None,
+            members_added: None,
         }
     }
 }
@@ -239,6 +247,8 @@ impl MockTeams {
             server,
             port,
             expectations: new_expectation_store(),
+            delay: ResponseDelay::default(),
+            clock: Clock::system(),
             sent_activities: Arc::new(Mutex::new(Vec::new())),
             conversations: Arc::new(Mutex::new(HashMap::new())),
             bot_id: Self::DEFAULT_BOT_ID.to_string(),
@@ -268,6 +278,8 @@ impl MockTeams {
             server,
             port,
             expectations: new_expectation_store(),
+            delay: ResponseDelay::default(),
+            clock: Clock::system(),
             sent_activities: Arc::new(Mutex::new(Vec::new())),
             conversations: Arc::new(Mutex::new(HashMap::new())),
             bot_id: bot_id.to_string(),
@@ -281,12 +293,23 @@ impl MockTeams {
         Ok(mock)
     }
 
+    #[must_use]
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    #[must_use]
+    pub const fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
     async fn setup_default_routes(&self) {
         let sent_activities = self.sent_activities.clone();
 
         Mock::given(method("POST"))
             .and(path_regex(r"/v3/conversations/.+/activities"))
-            .respond_with(move |req: &wiremock::Request| {
+            .respond_with(delayed(&self.delay, move |req: &wiremock::Request| {
                 let body: serde_json::Value = req.body_json().unwrap_or_default();
 
                 let activity = Activity {
@@ -313,6 +336,7 @@ impl MockTeams {
                     reply_to_id: None,
                     value: None,
                     name: None,
+                    members_added: None,
                 };
 
                 sent_activities.lock().unwrap().push(activity.clone());
@@ -320,41 +344,41 @@ impl MockTeams {
                 let response = ResourceResponse { id: activity.id };
 
                 ResponseTemplate::new(200).set_body_json(&response)
-            })
+            }))
             .mount(&self.server)
             .await;
 
         Mock::given(method("POST"))
             .and(path_regex(r"/v3/conversations/.+/activities/.+"))
-            .respond_with(|_req: &wiremock::Request| {
+            .respond_with(delayed(&self.delay, |_req: &wiremock::Request| {
                 let response = ResourceResponse {
                     id: Uuid::new_v4().to_string(),
                 };
                 ResponseTemplate::new(200).set_body_json(&response)
-            })
+            }))
             .mount(&self.server)
             .await;
 
         Mock::given(method("PUT"))
             .and(path_regex(r"/v3/conversations/.+/activities/.+"))
-            .respond_with(|_req: &wiremock::Request| {
+            .respond_with(delayed(&self.delay, |_req: &wiremock::Request| {
                 let response = ResourceResponse {
                     id: Uuid::new_v4().to_string(),
                 };
                 ResponseTemplate::new(200).set_body_json(&response)
-            })
+            }))
             .mount(&self.server)
             .await;
 
         Mock::given(method("DELETE"))
             .and(path_regex(r"/v3/conversations/.+/activities/.+"))
-            .respond_with(ResponseTemplate::new(200))
+            .respond_with(delayed(&self.delay, ResponseTemplate::new(200)))
             .mount(&self.server)
             .await;
 
         Mock::given(method("GET"))
             .and(path_regex(r"/v3/conversations/.+/members"))
-            .respond_with(|_req: &wiremock::Request| {
+            .respond_with(delayed(&self.delay, |_req: &wiremock::Request| {
                 let members = vec![TeamsChannelAccount {
                     id: "user-1".to_string(),
                     name: Some("Test User".to_string()),
@@ -366,13 +390,13 @@ impl MockTeams {
                     surname: Some("User".to_string()),
                 }];
                 ResponseTemplate::new(200).set_body_json(&members)
-            })
+            }))
             .mount(&self.server)
             .await;
 
         Mock::given(method("GET"))
             .and(path_regex(r"/v3/conversations/.+/members/.+"))
-            .respond_with(|_req: &wiremock::Request| {
+            .respond_with(delayed(&self.delay, |_req: &wiremock::Request| {
                 let member = TeamsChannelAccount {
                     id: "user-1".to_string(),
                     name: Some("Test User".to_string()),
@@ -384,13 +408,13 @@ impl MockTeams {
                     surname: Some("User".to_string()),
                 };
                 ResponseTemplate::new(200).set_body_json(&member)
-            })
+            }))
             .mount(&self.server)
             .await;
 
         Mock::given(method("POST"))
             .and(path("/v3/conversations"))
-            .respond_with(|_req: &wiremock::Request| {
+            .respond_with(delayed(&self.delay, |_req: &wiremock::Request| {
                 let conversation = ConversationAccount {
                     id: format!("conv-{}", Uuid::new_v4()),
                     name: None,
@@ -399,29 +423,32 @@ impl MockTeams {
                     tenant_id: Some("test-tenant".to_string()),
                 };
                 ResponseTemplate::new(200).set_body_json(&conversation)
-            })
+            }))
             .mount(&self.server)
             .await;
 
         Mock::given(method("GET"))
             .and(path("/v3/conversations"))
-            .respond_with(|_req: &wiremock::Request| {
+            .respond_with(delayed(&self.delay, |_req: &wiremock::Request| {
                 let result = ConversationsResult {
                     continuation_token: None,
                     conversations: vec![],
                 };
                 ResponseTemplate::new(200).set_body_json(&result)
-            })
+            }))
             .mount(&self.server)
             .await;
 
         Mock::given(method("POST"))
             .and(path("/botframework.com/oauth2/v2.0/token"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-                "token_type": "Bearer",
-                "expires_in": 3600,
-                "access_token": format!("test_token_{}", Uuid::new_v4())
-            })))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "token_type": "Bearer",
+                    "expires_in": 3600,
+                    "access_token": format!("test_token_{}", Uuid::new_v4())
+                })),
+            ))
             .mount(&self.server)
             .await;
     }
@@ -429,12 +456,13 @@ impl MockTeams {
     #[must_use]
     pub fn simulate_message(&self, from_id: &str, from_name: &str, text: &str) -> Activity {
         let conversation_id = format!("conv-{}", Uuid::new_v4());
+        let now = self.clock.now().to_rfc3339();
 
         Activity {
             kind: "message".to_string(),
             id: Uuid::new_v4().to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-            local_timestamp: Some(chrono::Utc::now().to_rfc3339()),
+            timestamp: now.clone(),
+            local_timestamp: Some(now),
             service_url: self.service_url.clone(),
             channel_id: "msteams".to_string(),
             from: ChannelAccount {
@@ -470,6 +498,7 @@ impl MockTeams {
             reply_to_id: None,
             value: None,
             name: None,
+            members_added: None,
         }
     }
 
@@ -502,7 +531,7 @@ impl MockTeams {
         Activity {
             kind: "conversationUpdate".to_string(),
             id: Uuid::new_v4().to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
+            timestamp: self.clock.now().to_rfc3339(),
             local_timestamp: None,
             service_url: self.service_url.clone(),
             channel_id: "msteams".to_string(),
@@ -540,6 +569,96 @@ impl MockTeams {
             reply_to_id: None,
             value: None,
             name: None,
+            members_added: None,
+        }
+    }
+
+    #[must_use]
+    pub fn simulate_team_installed(&self, team_id: &str) -> Activity {
+        self.team_conversation_update(
+            team_id,
+            serde_json::json!({
+                "tenant": {
+                    "id": self.tenant_id
+                },
+                "eventType": "teamMemberAdded",
+                "team": {
+                    "id": team_id
+                }
+            }),
+            Some(vec![self.bot_account()]),
+        )
+    }
+
+    #[must_use]
+    pub fn simulate_channel_created(&self, channel_id: &str) -> Activity {
+        let team_id = format!("19:{}@thread.tacv2", Uuid::new_v4().simple());
+
+        self.team_conversation_update(
+            &team_id,
+            serde_json::json!({
+                "tenant": {
+                    "id": self.tenant_id
+                },
+                "eventType": "channelCreated",
+                "channel": {
+                    "id": channel_id
+                },
+                "team": {
+                    "id": team_id
+                }
+            }),
+            None,
+        )
+    }
+
+    fn bot_account(&self) -> ChannelAccount {
+        ChannelAccount {
+            id: self.bot_id.clone(),
+            name: Some(self.bot_name.clone()),
+            aad_object_id: None,
+            role: Some("bot".to_string()),
+        }
+    }
+
+    fn team_conversation_update(
+        &self,
+        conversation_id: &str,
+        channel_data: serde_json::Value,
+        members_added: Option<Vec<ChannelAccount>>,
+    ) -> Activity {
+        Activity {
+            kind: "conversationUpdate".to_string(),
+            id: Uuid::new_v4().to_string(),
+            timestamp: self.clock.now().to_rfc3339(),
+            local_timestamp: None,
+            service_url: self.service_url.clone(),
+            channel_id: "msteams".to_string(),
+            from: ChannelAccount {
+                id: format!("29:{}", Uuid::new_v4()),
+                name: None,
+                aad_object_id: Some(Uuid::new_v4().to_string()),
+                role: None,
+            },
+            conversation: ConversationAccount {
+                id: conversation_id.to_string(),
+                name: None,
+                conversation_type: Some("channel".to_string()),
+                is_group: Some(true),
+                tenant_id: Some(self.tenant_id.clone()),
+            },
+            recipient: self.bot_account(),
+            text: None,
+            text_format: None,
+            locale: None,
+            attachments: None,
+            entities: None,
+            channel_data: Some(channel_data),
+            action: None,
+            reply_to_id: None,
+            value: None,
+            name: None,
+            members_added,
         }
     }
 
@@ -556,7 +675,7 @@ impl MockTeams {
         Activity {
             kind: "invoke".to_string(),
             id: Uuid::new_v4().to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
+            timestamp: self.clock.now().to_rfc3339(),
             local_timestamp: None,
             service_url: self.service_url.clone(),
             channel_id: "msteams".to_string(),
@@ -593,6 +712,7 @@ impl MockTeams {
             reply_to_id: None,
             value: Some(value),
             name: Some(name.to_string()),
+            members_added: None,
         }
     }
 
@@ -630,7 +750,7 @@ impl MockTeams {
         Activity {
             kind: "messageReaction".to_string(),
             id: Uuid::new_v4().to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
+            timestamp: self.clock.now().to_rfc3339(),
             local_timestamp: None,
             service_url: self.service_url.clone(),
             channel_id: "msteams".to_string(),
@@ -667,10 +787,67 @@ impl MockTeams {
                 }]
             })),
             name: None,
+            members_added: None,
+        }
+    }
+
+    #[must_use]
+    pub fn simulate_message_update(&self, original: &Activity, new_text: &str) -> Activity {
+        self.message_event("messageUpdate", "editMessage", original, Some(new_text))
+    }
+
+    #[must_use]
+    pub fn simulate_message_delete(&self, original: &Activity) -> Activity {
+        self.message_event("messageDelete", "softDeleteMessage", original, None)
+    }
+
+    fn message_event(
+        &self,
+        kind: &str,
+        event_type: &str,
+        original: &Activity,
+        text: Option<&str>,
+    ) -> Activity {
+        let now = self.clock.now().to_rfc3339();
+
+        Activity {
+            kind: kind.to_string(),
+            id: original.id.clone(),
+            timestamp: now.clone(),
+            local_timestamp: Some(now),
+            service_url: self.service_url.clone(),
+            channel_id: "msteams".to_string(),
+            from: original.from.clone(),
+            conversation: original.conversation.clone(),
+            recipient: self.bot_account(),
+            text: text.map(str::to_string),
+            text_format: text.map(|_| "plain".to_string()),
+            locale: Some("en-US".to_string()),
+            attachments: None,
+            entities: None,
+            channel_data: Some(serde_json::json!({
+                "tenant": {
+                    "id": self.tenant_id
+                },
+                "eventType": event_type
+            })),
+            action: None,
+            reply_to_id: original.reply_to_id.clone(),
+            value: None,
+            name: None,
+            members_added: None,
         }
     }
 
     pub async fn expect_error(&self, code: &str, message: &str) {
+        expect_request(
+            &self.expectations,
+            &format!("Teams error {code}"),
+            "POST",
+            r"^/v3/conversations/.+/activities$",
+            Some(1),
+        );
+
         let error_response = ErrorResponse {
             error: ErrorBody {
                 code: code.to_string(),
@@ -680,7 +857,10 @@ impl MockTeams {
 
         Mock::given(method("POST"))
             .and(path_regex(r"/v3/conversations/.+/activities"))
-            .respond_with(ResponseTemplate::new(400).set_body_json(&error_response))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(400).set_body_json(&error_response),
+            ))
             .mount(&self.server)
             .await;
     }
@@ -742,6 +922,11 @@ impl MockTeams {
         self.port
     }
 
+    #[must_use]
+    pub const fn response_delay(&self) -> &ResponseDelay {
+        &self.delay
+    }
+
     #[must_use]
     pub fn bot_id(&self) -> &str {
         &self.bot_id
@@ -776,6 +961,17 @@ impl MockTeams {
     pub async fn received_requests(&self) -> Vec<wiremock::Request> {
         self.server.received_requests().await.unwrap_or_default()
     }
+
+    pub fn expect(&self, expectation: Expectation) {
+        self.expectations
+            .lock()
+            .unwrap()
+            .insert(expectation.name.clone(), expectation);
+    }
+
+    pub async fn unexpected_requests(&self) -> Vec<wiremock::Request> {
+        uncovered_requests(self.received_requests().await, &self.expectations)
+    }
 }
 
 pub fn adaptive_card(content: serde_json::Value) -> Attachment {
@@ -867,6 +1063,7 @@ mod tests {
             reply_to_id: None,
             value: None,
             name: None,
+            members_added: None,
         };
 
         let json = serde_json::to_string(&activity).unwrap();
@@ -937,4 +1134,91 @@ mod tests {
         assert!(json.contains("BadRequest"));
         assert!(json.contains("Invalid activity"));
     }
+
+    #[tokio::test]
+    async fn test_activities_share_frozen_clock_timestamp() {
+        use chrono::TimeZone;
+
+        let start = chrono::Utc.with_ymd_and_hms(2024, 1, 15, 9, 30, 0).unwrap();
+        let clock = Clock::frozen(start);
+        let teams = MockTeams::start(crate::ports::PortAllocator::allocate())
+            .await
+            .unwrap()
+            .with_clock(clock.clone());
+
+        let message = teams.simulate_message("user-1", "Alice", "hello");
+        let added = teams.simulate_member_added("user-2", "Bob");
+        assert_eq!(message.timestamp, start.to_rfc3339());
+        assert_eq!(message.local_timestamp, Some(start.to_rfc3339()));
+        assert_eq!(added.timestamp, message.timestamp);
+
+        clock.advance(std::time::Duration::from_secs(60));
+        let later = teams.simulate_reaction("user-1", "Alice", &message.id, "like");
+        assert_eq!(
+            later.timestamp,
+            (start + chrono::Duration::seconds(60)).to_rfc3339()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_team_and_channel_install_events() {
+        let teams = MockTeams::start(crate::ports::PortAllocator::allocate())
+            .await
+            .unwrap();
+
+        let installed = teams.simulate_team_installed("19:team@thread.tacv2");
+        assert_eq!(installed.kind, "conversationUpdate");
+        assert_eq!(installed.conversation.id, "19:team@thread.tacv2");
+        assert_eq!(
+            installed.conversation.conversation_type.as_deref(),
+            Some("channel")
+        );
+        let channel_data = installed.channel_data.clone().unwrap();
+        assert_eq!(channel_data["eventType"], "teamMemberAdded");
+        assert_eq!(channel_data["team"]["id"], "19:team@thread.tacv2");
+        assert_eq!(channel_data["tenant"]["id"], teams.tenant_id());
+        let added = installed.members_added.clone().unwrap();
+        assert_eq!(added[0].id, teams.bot_id());
+
+        let json = serde_json::to_value(&installed).unwrap();
+        assert_eq!(json["membersAdded"][0]["id"], teams.bot_id());
+        assert_eq!(json["channelData"]["eventType"], "teamMemberAdded");
+
+        let created = teams.simulate_channel_created("19:support@thread.tacv2");
+        assert_eq!(created.kind, "conversationUpdate");
+        let channel_data = created.channel_data.unwrap();
+        assert_eq!(channel_data["eventType"], "channelCreated");
+        assert_eq!(channel_data["channel"]["id"], "19:support@thread.tacv2");
+        assert_eq!(channel_data["team"]["id"], created.conversation.id.as_str());
+        assert!(created.members_added.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_user_edit_and_delete_events() {
+        let teams = MockTeams::start(crate::ports::PortAllocator::allocate())
+            .await
+            .unwrap();
+        let original = teams.simulate_message("user-1", "Alice", "helo");
+
+        let edited = teams.simulate_message_update(&original, "hello");
+        assert_eq!(edited.kind, "messageUpdate");
+        assert_eq!(edited.id, original.id);
+        assert_eq!(edited.conversation.id, original.conversation.id);
+        assert_eq!(edited.from.id, "user-1");
+        assert_eq!(edited.text.as_deref(), Some("hello"));
+        assert_eq!(edited.channel_data.unwrap()["eventType"], "editMessage");
+
+        let deleted = teams.simulate_message_delete(&original);
+        assert_eq!(deleted.kind, "messageDelete");
+        assert_eq!(deleted.id, original.id);
+        assert_eq!(deleted.conversation.id, original.conversation.id);
+        assert_eq!(deleted.reply_to_id, original.reply_to_id);
+        assert!(deleted.text.is_none());
+
+        let json = serde_json::to_value(&deleted).unwrap();
+        assert_eq!(json["type"], "messageDelete");
+        assert_eq!(json["id"], original.id.as_str());
+        assert_eq!(json["channelData"]["eventType"], "softDeleteMessage");
+        assert!(json.get("text").is_none());
+    }
 }
@@ -1,4 +1,7 @@
-use super::{new_expectation_store, ExpectationStore};
+use super::delay::{delayed, ResponseDelay};
+use super::{
+    expect_request, new_expectation_store, uncovered_requests, Clock, Expectation, ExpectationStore,
+};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
@@ -6,15 +9,20 @@ use uuid::Uuid;
 use wiremock::matchers::{method, path_regex};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
+const MESSAGES_PATH: &str = r"^/v\d+\.\d+/\d+/messages$";
+
 pub struct MockWhatsApp {
     server: MockServer,
     port: u16,
     expectations: ExpectationStore,
+    delay: ResponseDelay,
+    clock: Clock,
     sent_messages: Arc<Mutex<Vec<SentMessage>>>,
     received_webhooks: Arc<Mutex<Vec<WebhookEvent>>>,
     phone_number_id: String,
     business_account_id: String,
     access_token: String,
+    verify_token: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -296,6 +304,50 @@ impl TemplateExpectation {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationRequest {
+    pub mode: String,
+    pub verify_token: String,
+    pub challenge: String,
+}
+
+impl VerificationRequest {
+    #[must_use]
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.verify_token = token.to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn query(&self) -> [(&str, &str); 3] {
+        [
+            ("hub.mode", self.mode.as_str()),
+            ("hub.verify_token", self.verify_token.as_str()),
+            ("hub.challenge", self.challenge.as_str()),
+        ]
+    }
+
+    pub fn url(&self, webhook_url: &str) -> Result<String> {
+        let url = reqwest::Url::parse_with_params(webhook_url, self.query())
+            .with_context(|| format!("Invalid webhook URL: {webhook_url}"))?;
+        Ok(url.to_string())
+    }
+
+    pub async fn is_accepted_by(&self, webhook_url: &str) -> Result<bool> {
+        let response = reqwest::Client::new()
+            .get(webhook_url)
+            .query(&self.query())
+            .send()
+            .await
+            .with_context(|| format!("Verification GET {webhook_url} failed"))?;
+        if !response.status().is_success() {
+            return Ok(false);
+        }
+        let body = response.text().await.unwrap_or_default();
+        Ok(body.trim() == self.challenge)
+    }
+}
+
 impl MockWhatsApp {
     pub const DEFAULT_PHONE_NUMBER_ID: &'static str = "123456789012345";
 
@@ -303,6 +355,8 @@ impl MockWhatsApp {
 
     pub const DEFAULT_ACCESS_TOKEN: &'static str = "test_access_token_12345";
 
+    pub const DEFAULT_VERIFY_TOKEN: &'static str = "test_verify_token";
+
     pub async fn start(port: u16) -> Result<Self> {
         let listener = std::net::TcpListener::bind(format!("127.0.0.1:{port}"))
             .context("Failed to bind MockWhatsApp port")?;
@@ -313,11 +367,14 @@ impl MockWhatsApp {
             server,
             port,
             expectations: new_expectation_store(),
+            delay: ResponseDelay::default(),
+            clock: Clock::system(),
             sent_messages: Arc::new(Mutex::new(Vec::new())),
             received_webhooks: Arc::new(Mutex::new(Vec::new())),
             phone_number_id: Self::DEFAULT_PHONE_NUMBER_ID.to_string(),
             business_account_id: Self::DEFAULT_BUSINESS_ACCOUNT_ID.to_string(),
             access_token: Self::DEFAULT_ACCESS_TOKEN.to_string(),
+            verify_token: Self::DEFAULT_VERIFY_TOKEN.to_string(),
         };
 
         mock.setup_default_routes().await;
@@ -340,11 +397,14 @@ impl MockWhatsApp {
             server,
             port,
             expectations: new_expectation_store(),
+            delay: ResponseDelay::default(),
+            clock: Clock::system(),
             sent_messages: Arc::new(Mutex::new(Vec::new())),
             received_webhooks: Arc::new(Mutex::new(Vec::new())),
             phone_number_id: phone_number_id.to_string(),
             business_account_id: business_account_id.to_string(),
             access_token: access_token.to_string(),
+            verify_token: Self::DEFAULT_VERIFY_TOKEN.to_string(),
         };
 
         mock.setup_default_routes().await;
@@ -352,12 +412,38 @@ impl MockWhatsApp {
         Ok(mock)
     }
 
+    #[must_use]
+    pub fn with_verify_token(mut self, token: &str) -> Self {
+        self.verify_token = token.to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn verification_request(&self, challenge: &str) -> VerificationRequest {
+        VerificationRequest {
+            mode: "subscribe".to_string(),
+            verify_token: self.verify_token.clone(),
+            challenge: challenge.to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    #[must_use]
+    pub const fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
     async fn setup_default_routes(&self) {
         let sent_messages = self.sent_messages.clone();
 
         Mock::given(method("POST"))
             .and(path_regex(r"/v\d+\.\d+/\d+/messages"))
-            .respond_with(move |req: &wiremock::Request| {
+            .respond_with(delayed(&self.delay, move |req: &wiremock::Request| {
                 let body: serde_json::Value = req.body_json().unwrap_or_default();
                 let to = body.get("to").and_then(|v| v.as_str()).unwrap_or("unknown");
                 let msg_type = body.get("type").and_then(|v| v.as_str()).unwrap_or("text");
@@ -438,50 +524,65 @@ impl MockWhatsApp {
                 };
 
                 ResponseTemplate::new(200).set_body_json(&response)
-            })
+            }))
             .mount(&self.server)
             .await;
 
         Mock::given(method("POST"))
             .and(path_regex(r"/v\d+\.\d+/\d+/media"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-                "id": format!("media_{}", Uuid::new_v4())
-            })))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "id": format!("media_{}", Uuid::new_v4())
+                })),
+            ))
             .mount(&self.server)
             .await;
 
         Mock::given(method("GET"))
             .and(path_regex(r"/v\d+\.\d+/\d+"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-                "url": "https://example.com/media/file.jpg",
-                "mime_type": "image/jpeg",
-                "sha256": "abc123",
-                "file_size": 12345,
-                "id": "media_123"
-            })))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "url": "https://example.com/media/file.jpg",
+                    "mime_type": "image/jpeg",
+                    "sha256": "abc123",
+                    "file_size": 12345,
+                    "id": "media_123"
+                })),
+            ))
             .mount(&self.server)
             .await;
 
         Mock::given(method("GET"))
             .and(path_regex(r"/v\d+\.\d+/\d+/whatsapp_business_profile"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-                "data": [{
-                    "messaging_product": "whatsapp",
-                    "address": "123 Test St",
-                    "description": "Test Business",
-                    "vertical": "OTHER",
-                    "email": "test@example.com",
-                    "websites": ["https://example.com"],
-                    "profile_picture_url": "https://example.com/pic.jpg"
-                }]
-            })))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "data": [{
+                        "messaging_product": "whatsapp",
+                        "address": "123 Test St",
+                        "description": "Test Business",
+                        "vertical": "OTHER",
+                        "email": "test@example.com",
+                        "websites": ["https://example.com"],
+                        "profile_picture_url": "https://example.com/pic.jpg"
+                    }]
+                })),
+            ))
             .mount(&self.server)
             .await;
     }
 
     #[must_use]
     pub fn expect_send_message(&self, to: &str) -> MessageExpectation {
-        let _ = self;
+        expect_request(
+            &self.expectations,
+            &format!("message to {to}"),
+            "POST",
+            MESSAGES_PATH,
+            Some(1),
+        );
         MessageExpectation {
             to: to.to_string(),
             message_type: None,
@@ -491,7 +592,13 @@ impl MockWhatsApp {
 
     #[must_use]
     pub fn expect_send_template(&self, name: &str) -> TemplateExpectation {
-        let _ = self;
+        expect_request(
+            &self.expectations,
+            &format!("template {name}"),
+            "POST",
+            MESSAGES_PATH,
+            Some(1),
+        );
         TemplateExpectation {
             name: name.to_string(),
             to: None,
@@ -501,11 +608,7 @@ impl MockWhatsApp {
 
     pub fn simulate_incoming(&self, from: &str, text: &str) -> Result<WebhookEvent> {
         let message_id = format!("wamid.{}", Uuid::new_v4().to_string().replace('-', ""));
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
-            .to_string();
+        let timestamp = self.clock.unix_timestamp().to_string();
 
         let event = WebhookEvent {
             object: "whatsapp_business_account".to_string(),
@@ -555,11 +658,7 @@ impl MockWhatsApp {
         caption: Option<&str>,
     ) -> Result<WebhookEvent> {
         let message_id = format!("wamid.{}", Uuid::new_v4().to_string().replace('-', ""));
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
-            .to_string();
+        let timestamp = self.clock.unix_timestamp().to_string();
 
         let event = WebhookEvent {
             object: "whatsapp_business_account".to_string(),
@@ -612,11 +711,7 @@ impl MockWhatsApp {
         button_text: &str,
     ) -> Result<WebhookEvent> {
         let message_id = format!("wamid.{}", Uuid::new_v4().to_string().replace('-', ""));
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
-            .to_string();
+        let timestamp = self.clock.unix_timestamp().to_string();
 
         let event = WebhookEvent {
             object: "whatsapp_business_account".to_string(),
@@ -675,11 +770,7 @@ impl MockWhatsApp {
         status: &str,
         recipient: &str,
     ) -> Result<WebhookEvent> {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
-            .to_string();
+        let timestamp = self.clock.unix_timestamp().to_string();
 
         let event = WebhookEvent {
             object: "whatsapp_business_account".to_string(),
@@ -722,6 +813,14 @@ impl MockWhatsApp {
     }
 
     pub async fn expect_error(&self, code: u32, message: &str) {
+        expect_request(
+            &self.expectations,
+            &format!("WhatsApp error {code}"),
+            "POST",
+            MESSAGES_PATH,
+            Some(1),
+        );
+
         let error_response = ErrorResponse {
             error: ErrorDetail {
                 message: message.to_string(),
@@ -733,7 +832,10 @@ impl MockWhatsApp {
 
         Mock::given(method("POST"))
             .and(path_regex(r"/v\d+\.\d+/\d+/messages"))
-            .respond_with(ResponseTemplate::new(400).set_body_json(&error_response))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(400).set_body_json(&error_response),
+            ))
             .mount(&self.server)
             .await;
     }
@@ -743,6 +845,14 @@ impl MockWhatsApp {
     }
 
     pub async fn expect_invalid_token(&self) {
+        expect_request(
+            &self.expectations,
+            "WhatsApp invalid token",
+            "POST",
+            MESSAGES_PATH,
+            Some(1),
+        );
+
         let error_response = ErrorResponse {
             error: ErrorDetail {
                 message: "Invalid OAuth access token".to_string(),
@@ -754,7 +864,10 @@ impl MockWhatsApp {
 
         Mock::given(method("POST"))
             .and(path_regex(r"/v\d+\.\d+/\d+/messages"))
-            .respond_with(ResponseTemplate::new(401).set_body_json(&error_response))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(401).set_body_json(&error_response),
+            ))
             .mount(&self.server)
             .await;
     }
@@ -799,6 +912,11 @@ impl MockWhatsApp {
         self.port
     }
 
+    #[must_use]
+    pub const fn response_delay(&self) -> &ResponseDelay {
+        &self.delay
+    }
+
     #[must_use]
     pub fn phone_number_id(&self) -> &str {
         &self.phone_number_id
@@ -814,6 +932,11 @@ impl MockWhatsApp {
         &self.access_token
     }
 
+    #[must_use]
+    pub fn verify_token(&self) -> &str {
+        &self.verify_token
+    }
+
     pub fn verify(&self) -> Result<()> {
         let store = self.expectations.lock().unwrap();
         for (_, exp) in store.iter() {
@@ -833,12 +956,60 @@ impl MockWhatsApp {
     pub async fn received_requests(&self) -> Vec<wiremock::Request> {
         self.server.received_requests().await.unwrap_or_default()
     }
+
+    pub fn expect(&self, expectation: Expectation) {
+        self.expectations
+            .lock()
+            .unwrap()
+            .insert(expectation.name.clone(), expectation);
+    }
+
+    pub async fn unexpected_requests(&self) -> Vec<wiremock::Request> {
+        uncovered_requests(self.received_requests().await, &self.expectations)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_verification_request_against_webhook_handler() {
+        let botserver = wiremock::MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/webhook/whatsapp$"))
+            .respond_with(|req: &wiremock::Request| {
+                let params: std::collections::HashMap<String, String> =
+                    req.url.query_pairs().into_owned().collect();
+                let token_ok = params.get("hub.verify_token").map(String::as_str) == Some("secret");
+                let subscribe = params.get("hub.mode").map(String::as_str) == Some("subscribe");
+                match params.get("hub.challenge") {
+                    Some(challenge) if token_ok && subscribe => {
+                        ResponseTemplate::new(200).set_body_string(challenge.clone())
+                    }
+                    _ => ResponseTemplate::new(403),
+                }
+            })
+            .mount(&botserver)
+            .await;
+        let webhook_url = format!("{}/webhook/whatsapp", botserver.uri());
+
+        let whatsapp = MockWhatsApp::start(crate::ports::PortAllocator::allocate())
+            .await
+            .unwrap()
+            .with_verify_token("secret");
+        let request = whatsapp.verification_request("1158201444");
+        assert_eq!(request.verify_token, "secret");
+        assert!(request
+            .url(&webhook_url)
+            .unwrap()
+            .ends_with("?hub.mode=subscribe&hub.verify_token=secret&hub.challenge=1158201444"));
+        assert!(request.is_accepted_by(&webhook_url).await.unwrap());
+
+        let mismatched = request.with_token("wrong");
+        assert!(!mismatched.is_accepted_by(&webhook_url).await.unwrap());
+    }
+
     #[test]
     fn test_message_type_serialization() {
         let msg_type = MessageType::Template;
@@ -1,9 +1,12 @@
-use super::{new_expectation_store, ExpectationStore};
+use super::delay::{delayed, ResponseDelay};
+use super::{
+    expect_request, new_expectation_store, uncovered_requests, Clock, Expectation, ExpectationStore,
+};
+use crate::services::{retry_with_backoff, STARTUP_RETRY_ATTEMPTS, STARTUP_RETRY_BACKOFF};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 use wiremock::matchers::{body_string_contains, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -12,6 +15,8 @@ pub struct MockZitadel {
     server: MockServer,
     port: u16,
     expectations: ExpectationStore,
+    delay: ResponseDelay,
+    clock: Clock,
     users: Arc<Mutex<HashMap<String, TestUser>>>,
     tokens: Arc<Mutex<HashMap<String, TokenInfo>>>,
     issuer: String,
@@ -121,8 +126,15 @@ struct ErrorResponse {
 
 impl MockZitadel {
     pub async fn start(port: u16) -> Result<Self> {
-        let listener = std::net::TcpListener::bind(format!("127.0.0.1:{port}"))
-            .context("Failed to bind MockZitadel port")?;
+        let listener = retry_with_backoff(
+            STARTUP_RETRY_ATTEMPTS,
+            STARTUP_RETRY_BACKOFF,
+            |_| async move {
+                std::net::TcpListener::bind(format!("127.0.0.1:{port}"))
+                    .context("Failed to bind MockZitadel port")
+            },
+        )
+        .await?;
 
         let server = MockServer::builder().listener(listener).start().await;
         let issuer = format!("http://127.0.0.1:{port}");
@@ -131,6 +143,8 @@ impl MockZitadel {
             server,
             port,
             expectations: new_expectation_store(),
+            delay: ResponseDelay::default(),
+            clock: Clock::system(),
             users: Arc::new(Mutex::new(HashMap::new())),
             tokens: Arc::new(Mutex::new(HashMap::new())),
             issuer,
@@ -142,6 +156,17 @@ impl MockZitadel {
         Ok(mock)
     }
 
+    #[must_use]
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    #[must_use]
+    pub const fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
     async fn setup_discovery_endpoint(&self) {
         let base_url = self.url();
 
@@ -190,7 +215,10 @@ impl MockZitadel {
 
         Mock::given(method("GET"))
             .and(path("/.well-known/openid-configuration"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&discovery))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&discovery),
+            ))
             .mount(&self.server)
             .await;
     }
@@ -209,7 +237,10 @@ impl MockZitadel {
 
         Mock::given(method("GET"))
             .and(path("/oauth/v2/keys"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&jwks))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&jwks),
+            ))
             .mount(&self.server)
             .await;
     }
@@ -241,6 +272,14 @@ impl MockZitadel {
     }
 
     pub async fn expect_login(&self, email: &str, password: &str) -> String {
+        expect_request(
+            &self.expectations,
+            &format!("login for {email}"),
+            "POST",
+            r"^/oauth/v2/token$",
+            Some(1),
+        );
+
         let user = self
             .users
             .lock()
@@ -262,10 +301,7 @@ impl MockZitadel {
 
         let access_token = format!("test_access_{}", Uuid::new_v4());
         let refresh_token = format!("test_refresh_{}", Uuid::new_v4());
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let now = self.clock.unix_timestamp();
         let expires_in = 3600u64;
 
         self.tokens.lock().unwrap().insert(
@@ -296,7 +332,10 @@ impl MockZitadel {
         Mock::given(method("POST"))
             .and(path("/oauth/v2/token"))
             .and(body_string_contains(format!("username={email}")))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&token_response))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&token_response),
+            ))
             .mount(&self.server)
             .await;
 
@@ -304,6 +343,14 @@ impl MockZitadel {
     }
 
     pub async fn expect_token_refresh(&self) {
+        expect_request(
+            &self.expectations,
+            "token refresh",
+            "POST",
+            r"^/oauth/v2/token$",
+            Some(1),
+        );
+
         let access_token = format!("test_access_{}", Uuid::new_v4());
         let refresh_token = format!("test_refresh_{}", Uuid::new_v4());
 
@@ -319,17 +366,25 @@ impl MockZitadel {
         Mock::given(method("POST"))
             .and(path("/oauth/v2/token"))
             .and(body_string_contains("grant_type=refresh_token"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&token_response))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&token_response),
+            ))
             .mount(&self.server)
             .await;
     }
 
     pub async fn expect_introspect(&self, token: &str, active: bool) {
+        expect_request(
+            &self.expectations,
+            "token introspection",
+            "POST",
+            r"^/oauth/v2/introspect$",
+            Some(1),
+        );
+
         let response = if active {
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
+            let now = self.clock.unix_timestamp();
 
             IntrospectionResponse {
                 active: true,
@@ -361,16 +416,24 @@ impl MockZitadel {
         Mock::given(method("POST"))
             .and(path("/oauth/v2/introspect"))
             .and(body_string_contains(format!("token={token}")))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&response),
+            ))
             .mount(&self.server)
             .await;
     }
 
     pub async fn expect_any_introspect_active(&self) {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        expect_request(
+            &self.expectations,
+            "any token introspection",
+            "POST",
+            r"^/oauth/v2/introspect$",
+            None,
+        );
+
+        let now = self.clock.unix_timestamp();
 
         let response = IntrospectionResponse {
             active: true,
@@ -387,12 +450,23 @@ impl MockZitadel {
 
         Mock::given(method("POST"))
             .and(path("/oauth/v2/introspect"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&response),
+            ))
             .mount(&self.server)
             .await;
     }
 
     pub async fn expect_userinfo(&self, token: &str, user: &TestUser) {
+        expect_request(
+            &self.expectations,
+            &format!("userinfo for {}", user.email),
+            "GET",
+            r"^/oidc/v1/userinfo$",
+            Some(1),
+        );
+
         let response = UserInfoResponse {
             sub: user.id.clone(),
             email: user.email.clone(),
@@ -405,12 +479,23 @@ impl MockZitadel {
         Mock::given(method("GET"))
             .and(path("/oidc/v1/userinfo"))
             .and(header("authorization", format!("Bearer {token}").as_str()))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&response),
+            ))
             .mount(&self.server)
             .await;
     }
 
     pub async fn expect_any_userinfo(&self) {
+        expect_request(
+            &self.expectations,
+            "any userinfo",
+            "GET",
+            r"^/oidc/v1/userinfo$",
+            None,
+        );
+
         let response = UserInfoResponse {
             sub: Uuid::new_v4().to_string(),
             email: "test@example.com".to_string(),
@@ -422,20 +507,39 @@ impl MockZitadel {
 
         Mock::given(method("GET"))
             .and(path("/oidc/v1/userinfo"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(&response))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(200).set_body_json(&response),
+            ))
             .mount(&self.server)
             .await;
     }
 
     pub async fn expect_revoke(&self) {
+        expect_request(
+            &self.expectations,
+            "token revocation",
+            "POST",
+            r"^/oauth/v2/revoke$",
+            Some(1),
+        );
+
         Mock::given(method("POST"))
             .and(path("/oauth/v2/revoke"))
-            .respond_with(ResponseTemplate::new(200))
+            .respond_with(delayed(&self.delay, ResponseTemplate::new(200)))
             .mount(&self.server)
             .await;
     }
 
     pub async fn expect_auth_error(&self, error: &str, description: &str) {
+        expect_request(
+            &self.expectations,
+            &format!("auth error {error}"),
+            "POST",
+            r"^/oauth/v2/token$",
+            Some(1),
+        );
+
         let response = ErrorResponse {
             error: error.to_string(),
             error_description: description.to_string(),
@@ -443,7 +547,10 @@ impl MockZitadel {
 
         Mock::given(method("POST"))
             .and(path("/oauth/v2/token"))
-            .respond_with(ResponseTemplate::new(401).set_body_json(&response))
+            .respond_with(delayed(
+                &self.delay,
+                ResponseTemplate::new(401).set_body_json(&response),
+            ))
             .mount(&self.server)
             .await;
     }
@@ -459,10 +566,7 @@ impl MockZitadel {
     }
 
     fn create_mock_id_token(&self, user: &TestUser) -> String {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let now = self.clock.unix_timestamp();
 
         let header = base64_url_encode(r#"{"alg":"RS256","typ":"JWT"}"#);
         let payload = base64_url_encode(
@@ -486,10 +590,7 @@ impl MockZitadel {
     #[must_use]
     pub fn generate_token(&self, user: &TestUser) -> String {
         let access_token = format!("test_access_{}", Uuid::new_v4());
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let now = self.clock.unix_timestamp();
 
         self.tokens.lock().unwrap().insert(
             access_token.clone(),
@@ -531,6 +632,11 @@ impl MockZitadel {
         self.port
     }
 
+    #[must_use]
+    pub const fn response_delay(&self) -> &ResponseDelay {
+        &self.delay
+    }
+
     #[must_use]
     pub fn discovery_url(&self) -> String {
         format!("{}/.well-known/openid-configuration", self.url())
@@ -556,6 +662,17 @@ impl MockZitadel {
     pub async fn received_requests(&self) -> Vec<wiremock::Request> {
         self.server.received_requests().await.unwrap_or_default()
     }
+
+    pub fn expect(&self, expectation: Expectation) {
+        self.expectations
+            .lock()
+            .unwrap()
+            .insert(expectation.name.clone(), expectation);
+    }
+
+    pub async fn unexpected_requests(&self) -> Vec<wiremock::Request> {
+        uncovered_requests(self.received_requests().await, &self.expectations)
+    }
 }
 
 fn base64_url_encode(input: &str) -> String {
@@ -689,4 +806,18 @@ mod tests {
         assert!(json.contains(r#""active":false"#));
         assert!(!json.contains("scope"));
     }
+
+    #[tokio::test]
+    async fn test_start_waits_for_port_held_by_previous_run() {
+        let previous = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = previous.local_addr().unwrap().port();
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            drop(previous);
+        });
+
+        let mock = MockZitadel::start(port).await.unwrap();
+        assert_eq!(mock.port, port);
+        release.await.unwrap();
+    }
 }
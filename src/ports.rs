@@ -1,5 +1,6 @@
 
 use std::collections::HashSet;
+use std::net::TcpListener;
 use std::sync::atomic::{AtomicU16, Ordering};
 use std::sync::Mutex;
 
@@ -17,15 +18,38 @@ impl PortAllocator {
                 continue;
             }
 
-            if Self::is_available(port) {
-                let mut guard = ALLOCATED_PORTS.lock().unwrap();
-                let set = guard.get_or_insert_with(HashSet::new);
-                set.insert(port);
+            if Self::is_available(port) && Self::mark_allocated(port) {
                 return port;
             }
         }
     }
 
+    pub fn allocate_bound() -> (u16, TcpListener) {
+        loop {
+            let port = PORT_COUNTER.fetch_add(1, Ordering::SeqCst);
+            if port > 60000 {
+                PORT_COUNTER.store(15000, Ordering::SeqCst);
+                continue;
+            }
+
+            if let Ok(listener) = TcpListener::bind(("127.0.0.1", port)) {
+                if Self::mark_allocated(port) {
+                    return (port, listener);
+                }
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn reserve(count: usize) -> Vec<(u16, TcpListener)> {
+        (0..count).map(|_| Self::allocate_bound()).collect()
+    }
+
+    fn mark_allocated(port: u16) -> bool {
+        let mut guard = ALLOCATED_PORTS.lock().unwrap();
+        guard.get_or_insert_with(HashSet::new).insert(port)
+    }
+
     #[must_use]
     pub fn allocate_range(count: usize) -> Vec<u16> {
         (0..count).map(|_| Self::allocate()).collect()
@@ -39,7 +63,6 @@ impl PortAllocator {
     }
 
     fn is_available(port: u16) -> bool {
-        use std::net::TcpListener;
         TcpListener::bind(("127.0.0.1", port)).is_ok()
     }
 }
@@ -52,6 +75,8 @@ pub struct TestPorts {
     pub botserver: u16,
     pub mock_zitadel: u16,
     pub mock_llm: u16,
+    pub mock_sms: u16,
+    pub mock_email: u16,
 }
 
 impl TestPorts {
@@ -63,6 +88,8 @@ impl TestPorts {
             botserver: PortAllocator::allocate(),
             mock_zitadel: PortAllocator::allocate(),
             mock_llm: PortAllocator::allocate(),
+            mock_sms: PortAllocator::allocate(),
+            mock_email: PortAllocator::allocate(),
         }
     }
 }
@@ -87,6 +114,12 @@ impl Drop for TestPorts {
         if self.mock_llm >= 15000 {
             PortAllocator::release(self.mock_llm);
         }
+        if self.mock_sms >= 15000 {
+            PortAllocator::release(self.mock_sms);
+        }
+        if self.mock_email >= 15000 {
+            PortAllocator::release(self.mock_email);
+        }
     }
 }
 
@@ -109,4 +142,35 @@ mod tests {
         assert_ne!(ports.postgres, ports.minio);
         assert_ne!(ports.redis, ports.botserver);
     }
+
+    #[test]
+    fn test_allocate_bound_holds_port() {
+        let (port, listener) = PortAllocator::allocate_bound();
+        assert_eq!(listener.local_addr().unwrap().port(), port);
+        assert!(TcpListener::bind(("127.0.0.1", port)).is_err());
+        PortAllocator::release(port);
+    }
+
+    #[test]
+    fn test_concurrent_allocation_is_unique() {
+        let handles: Vec<_> = (0..50)
+            .map(|_| std::thread::spawn(PortAllocator::allocate_bound))
+            .collect();
+        let reserved: Vec<(u16, TcpListener)> =
+            handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        let unique: HashSet<u16> = reserved.iter().map(|(port, _)| *port).collect();
+        assert_eq!(unique.len(), 50);
+
+        for (port, _) in &reserved {
+            PortAllocator::release(*port);
+        }
+    }
+
+    #[test]
+    fn test_reserve_batch() {
+        let reserved = PortAllocator::reserve(5);
+        let unique: HashSet<u16> = reserved.iter().map(|(port, _)| *port).collect();
+        assert_eq!(unique.len(), 5);
+    }
 }
@@ -0,0 +1,161 @@
+use anyhow::{Context, Result};
+use reqwest::{Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    client: reqwest::Client,
+    base_url: String,
+    bearer_token: Option<String>,
+}
+
+impl ApiClient {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new(base_url: &str) -> Result<Self> {
+        Self::with_timeout(base_url, Self::DEFAULT_TIMEOUT)
+    }
+
+    pub fn with_timeout(base_url: &str, timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .timeout(timeout)
+            .build()
+            .context("Failed to build API client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            bearer_token: None,
+        })
+    }
+
+    #[must_use]
+    pub fn with_bearer_token(mut self, token: &str) -> Self {
+        self.bearer_token = Some(token.to_string());
+        self
+    }
+
+    pub fn set_bearer_token(&mut self, token: Option<&str>) {
+        self.bearer_token = token.map(str::to_string);
+    }
+
+    #[must_use]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    #[must_use]
+    pub fn url(&self, path: &str) -> String {
+        if path.is_empty() {
+            return self.base_url.clone();
+        }
+        format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+    }
+
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self.client.request(method, self.url(path));
+        match &self.bearer_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    pub async fn get(&self, path: &str) -> Result<Response> {
+        self.request(Method::GET, path)
+            .send()
+            .await
+            .with_context(|| format!("GET {} failed", self.url(path)))
+    }
+
+    pub async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let response = self.get(path).await?;
+        Self::parse_json(response).await
+    }
+
+    pub async fn post_json<B, T>(&self, path: &str, body: &B) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let response = self
+            .request(Method::POST, path)
+            .json(body)
+            .send()
+            .await
+            .with_context(|| format!("POST {} failed", self.url(path)))?;
+        Self::parse_json(response).await
+    }
+
+    pub async fn is_healthy(&self) -> bool {
+        self.get("/health")
+            .await
+            .is_ok_and(|resp| resp.status().is_success())
+    }
+
+    async fn parse_json<T: DeserializeOwned>(response: Response) -> Result<T> {
+        let status = response.status();
+        let url = response.url().to_string();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("{url} returned {status}: {body}");
+        }
+        response
+            .json()
+            .await
+            .with_context(|| format!("Failed to decode JSON from {url}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_api_client_json_and_bearer_auth() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/bots"))
+            .and(header("Authorization", "Bearer test_access_token"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{"name": "support"}])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/bots"))
+            .and(body_json(serde_json::json!({"name": "sales"})))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({"id": 7, "name": "sales"})),
+            )
+            .mount(&server)
+            .await;
+
+        let anonymous = ApiClient::new(&format!("{}/", server.uri())).unwrap();
+        assert_eq!(
+            anonymous.url("/api/bots"),
+            format!("{}/api/bots", server.uri())
+        );
+        let err = anonymous
+            .get_json::<serde_json::Value>("/api/bots")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("404"));
+        assert!(!anonymous.is_healthy().await);
+
+        let client = anonymous.with_bearer_token("test_access_token");
+        let bots: Vec<serde_json::Value> = client.get_json("/api/bots").await.unwrap();
+        assert_eq!(bots[0]["name"], "support");
+
+        let created: serde_json::Value = client
+            .post_json("api/bots", &serde_json::json!({"name": "sales"}))
+            .await
+            .unwrap();
+        assert_eq!(created["id"], 7);
+    }
+}
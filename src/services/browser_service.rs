@@ -4,6 +4,7 @@ use std::process::{Child, Command, Stdio};
 use tokio::time::{sleep, Duration};
 
 pub const DEFAULT_DEBUG_PORT: u16 = 9222;
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
 
 pub struct BrowserService {
     port: u16,
@@ -13,6 +14,11 @@ pub struct BrowserService {
 }
 
 impl BrowserService {
+    pub async fn start_on_free_port() -> Result<Self> {
+        let port = crate::ports::PortAllocator::allocate();
+        Self::start(port).await
+    }
+
     pub async fn start(port: u16) -> Result<Self> {
         let _ = std::process::Command::new("pkill")
             .args(["-9", "-f", &format!("--remote-debugging-port={port}")])
@@ -80,7 +86,7 @@ impl BrowserService {
 
         for i in 0..100 {
             sleep(Duration::from_millis(100)).await;
-            if service.is_ready().await {
+            if service.is_healthy().await {
                 info!("Browser CDP ready on port {port}");
                 println!("  ✓ Browser CDP ready on port {port}");
                 return Ok(service);
@@ -96,14 +102,27 @@ impl BrowserService {
         Ok(service)
     }
 
-    async fn is_ready(&self) -> bool {
-        let url = format!("http://127.0.0.1:{}/json/version", self.port);
-        match reqwest::get(&url).await {
+    pub async fn is_healthy(&self) -> bool {
+        let Ok(client) = reqwest::Client::builder()
+            .timeout(HEALTH_PROBE_TIMEOUT)
+            .build()
+        else {
+            return false;
+        };
+        let url = format!("{}/json/version", self.http_url());
+        match client.get(&url).send().await {
             Ok(resp) => resp.status().is_success(),
             Err(_) => false,
         }
     }
 
+    pub async fn restart(&mut self) -> Result<()> {
+        warn!("Restarting browser on CDP port {}", self.port);
+        self.cleanup();
+        *self = Self::start(self.port).await?;
+        Ok(())
+    }
+
     fn detect_browser_binary() -> Result<String> {
         if let Ok(path) = std::env::var("BROWSER_BINARY") {
             if std::path::Path::new(&path).exists() {
@@ -205,6 +224,43 @@ impl Drop for BrowserService {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_health_probe_fails_on_dead_port() {
+        let port = crate::ports::PortAllocator::allocate();
+        let service = BrowserService {
+            port,
+            process: None,
+            binary_path: String::new(),
+            user_data_dir: format!("/tmp/browser-cdp-health-{port}"),
+        };
+        assert!(!service.is_healthy().await);
+    }
+
+    #[tokio::test]
+    async fn test_start_on_free_port_uses_distinct_ports() {
+        if BrowserService::detect_browser_binary().is_err() {
+            eprintln!("Skipping: no browser binary found");
+            return;
+        }
+
+        let (first, second) = tokio::join!(
+            BrowserService::start_on_free_port(),
+            BrowserService::start_on_free_port()
+        );
+        let (Ok(mut first), Ok(mut second)) = (first, second) else {
+            eprintln!("Skipping: failed to start browser services");
+            return;
+        };
+
+        assert_ne!(first.port(), second.port());
+        assert_ne!(first.port(), DEFAULT_DEBUG_PORT);
+        assert!(first.is_healthy().await);
+        assert!(second.is_healthy().await);
+
+        first.stop().await.unwrap();
+        second.stop().await.unwrap();
+    }
+
     #[test]
     fn test_detect_browser() {
         let result = BrowserService::detect_browser_binary();
@@ -1,5 +1,10 @@
 use super::{check_tcp_port, ensure_dir, wait_for, HEALTH_CHECK_INTERVAL, HEALTH_CHECK_TIMEOUT};
+use crate::error::{ensure_port_free, HarnessError};
 use anyhow::{Context, Result};
+use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_s3::operation::create_bucket::CreateBucketError;
+use aws_sdk_s3::operation::head_bucket::HeadBucketError;
+use aws_sdk_s3::primitives::ByteStream;
 #[cfg(unix)]
 use nix::sys::signal::{kill, Signal};
 #[cfg(unix)]
@@ -25,6 +30,8 @@ impl MinioService {
 
     pub const DEFAULT_SECRET_KEY: &'static str = "minioadmin";
 
+    pub const REGION: &'static str = "us-east-1";
+
     fn find_minio_binary() -> Result<PathBuf> {
         if let Ok(stack_path) = std::env::var("BOTSERVER_STACK_PATH") {
             let minio_path = PathBuf::from(&stack_path).join("bin/drive/minio");
@@ -75,29 +82,14 @@ impl MinioService {
         anyhow::bail!("MinIO not found. Install MinIO or set BOTSERVER_STACK_PATH env var")
     }
 
-    pub async fn start(api_port: u16, data_dir: &str) -> Result<Self> {
-        let bin_path = Self::find_minio_binary()?;
-        log::info!("Using MinIO from: {}", bin_path.display());
-
-        let data_path = PathBuf::from(data_dir).join("minio");
-        ensure_dir(&data_path)?;
-
-        let console_port = api_port + 1000;
-
-        let mut service = Self {
+    pub async fn start(api_port: u16, data_dir: &str) -> Result<Self, HarnessError> {
+        Self::start_with_credentials(
             api_port,
-            console_port,
-            data_dir: data_path,
-            bin_path,
-            process: None,
-            access_key: Self::DEFAULT_ACCESS_KEY.to_string(),
-            secret_key: Self::DEFAULT_SECRET_KEY.to_string(),
-        };
-
-        service.start_server()?;
-        service.wait_ready().await?;
-
-        Ok(service)
+            data_dir,
+            Self::DEFAULT_ACCESS_KEY,
+            Self::DEFAULT_SECRET_KEY,
+        )
+        .await
     }
 
     pub async fn start_with_credentials(
@@ -105,15 +97,18 @@ impl MinioService {
         data_dir: &str,
         access_key: &str,
         secret_key: &str,
-    ) -> Result<Self> {
-        let bin_path = Self::find_minio_binary()?;
+    ) -> Result<Self, HarnessError> {
+        let bin_path =
+            Self::find_minio_binary().map_err(|e| HarnessError::binary_not_found("MinIO", &e))?;
         log::info!("Using MinIO from: {}", bin_path.display());
 
+        let console_port = api_port + 1000;
+        ensure_port_free("MinIO", api_port)?;
+        ensure_port_free("MinIO", console_port)?;
+
         let data_path = PathBuf::from(data_dir).join("minio");
         ensure_dir(&data_path)?;
 
-        let console_port = api_port + 1000;
-
         let mut service = Self {
             api_port,
             console_port,
@@ -125,7 +120,13 @@ impl MinioService {
         };
 
         service.start_server()?;
-        service.wait_ready().await?;
+        service
+            .wait_ready()
+            .await
+            .map_err(|_| HarnessError::HealthTimeout {
+                service: "MinIO",
+                timeout: HEALTH_CHECK_TIMEOUT,
+            })?;
 
         Ok(service)
     }
@@ -179,66 +180,53 @@ impl MinioService {
         Ok(())
     }
 
-    pub async fn create_bucket(&self, name: &str) -> Result<()> {
-        log::info!("Creating bucket '{name}'");
-
-        if let Ok(mc) = Self::find_mc_binary() {
-            let alias_name = format!("test{}", self.api_port);
-            let _ = Command::new(&mc)
-                .args([
-                    "alias",
-                    "set",
-                    &alias_name,
-                    &self.endpoint(),
-                    &self.access_key,
-                    &self.secret_key,
-                ])
-                .output();
-
-            let output = Command::new(&mc)
-                .args(["mb", "--ignore-existing", &format!("{alias_name}/{name}")])
-                .output()?;
-
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                if !stderr.contains("already") {
-                    anyhow::bail!("Failed to create bucket: {stderr}");
-                }
-            }
+    #[must_use]
+    pub fn s3_client(&self) -> aws_sdk_s3::Client {
+        let credentials =
+            Credentials::new(&self.access_key, &self.secret_key, None, None, "bottest");
+        let config = aws_sdk_s3::config::Builder::new()
+            .behavior_version(BehaviorVersion::latest())
+            .endpoint_url(self.endpoint())
+            .region(Region::new(Self::REGION))
+            .credentials_provider(credentials)
+            .force_path_style(true)
+            .build();
+        aws_sdk_s3::Client::from_conf(config)
+    }
 
+    pub async fn ensure_bucket(&self, name: &str) -> Result<()> {
+        if self.bucket_exists(name).await? {
             return Ok(());
         }
+        self.create_bucket(name).await
+    }
 
-        let url = format!("{}/{}", self.endpoint(), name);
-        let client = reqwest::Client::new();
-        let resp = client
-            .put(&url)
-            .basic_auth(&self.access_key, Some(&self.secret_key))
-            .send()
-            .await?;
+    pub async fn create_bucket(&self, name: &str) -> Result<()> {
+        log::info!("Creating bucket '{name}'");
 
-        if !resp.status().is_success() && resp.status().as_u16() != 409 {
-            anyhow::bail!("Failed to create bucket: {}", resp.status());
+        match self.s3_client().create_bucket().bucket(name).send().await {
+            Ok(_) => Ok(()),
+            Err(e)
+                if e.as_service_error()
+                    .is_some_and(CreateBucketError::is_bucket_already_owned_by_you) =>
+            {
+                Ok(())
+            }
+            Err(e) => Err(e).with_context(|| format!("Failed to create bucket '{name}'")),
         }
-
-        Ok(())
     }
 
     pub async fn put_object(&self, bucket: &str, key: &str, data: &[u8]) -> Result<()> {
         log::debug!("Putting object '{}/{}' ({} bytes)", bucket, key, data.len());
 
-        let url = format!("{}/{}/{}", self.endpoint(), bucket, key);
-        let client = reqwest::Client::new();
-        let resp = client
-            .put(&url)
-            .basic_auth(&self.access_key, Some(&self.secret_key))
-            .body(data.to_vec())
+        self.s3_client()
+            .put_object()
+            .bucket(bucket)
+            .key(key)
+            .body(ByteStream::from(data.to_vec()))
             .send()
-            .await?;
-
-        if !resp.status().is_success() {
-            anyhow::bail!("Failed to put object: {}", resp.status());
-        }
+            .await
+            .with_context(|| format!("Failed to put object '{bucket}/{key}'"))?;
 
         Ok(())
     }
@@ -246,35 +234,33 @@ impl MinioService {
     pub async fn get_object(&self, bucket: &str, key: &str) -> Result<Vec<u8>> {
         log::debug!("Getting object '{bucket}/{key}'");
 
-        let url = format!("{}/{}/{}", self.endpoint(), bucket, key);
-        let client = reqwest::Client::new();
-        let resp = client
-            .get(&url)
-            .basic_auth(&self.access_key, Some(&self.secret_key))
+        let output = self
+            .s3_client()
+            .get_object()
+            .bucket(bucket)
+            .key(key)
             .send()
-            .await?;
-
-        if !resp.status().is_success() {
-            anyhow::bail!("Failed to get object: {}", resp.status());
-        }
-
-        Ok(resp.bytes().await?.to_vec())
+            .await
+            .with_context(|| format!("Failed to get object '{bucket}/{key}'"))?;
+        let body = output
+            .body
+            .collect()
+            .await
+            .with_context(|| format!("Failed to read object '{bucket}/{key}'"))?;
+
+        Ok(body.into_bytes().to_vec())
     }
 
     pub async fn delete_object(&self, bucket: &str, key: &str) -> Result<()> {
         log::debug!("Deleting object '{bucket}/{key}'");
 
-        let url = format!("{}/{}/{}", self.endpoint(), bucket, key);
-        let client = reqwest::Client::new();
-        let resp = client
-            .delete(&url)
-            .basic_auth(&self.access_key, Some(&self.secret_key))
+        self.s3_client()
+            .delete_object()
+            .bucket(bucket)
+            .key(key)
             .send()
-            .await?;
-
-        if !resp.status().is_success() && resp.status().as_u16() != 404 {
-            anyhow::bail!("Failed to delete object: {}", resp.status());
-        }
+            .await
+            .with_context(|| format!("Failed to delete object '{bucket}/{key}'"))?;
 
         Ok(())
     }
@@ -282,65 +268,54 @@ impl MinioService {
     pub async fn list_objects(&self, bucket: &str, prefix: Option<&str>) -> Result<Vec<String>> {
         log::debug!("Listing objects in bucket '{bucket}'");
 
-        let mut url = format!("{}/{}", self.endpoint(), bucket);
-        if let Some(p) = prefix {
-            url = format!("{url}?prefix={p}");
-        }
-
-        let client = reqwest::Client::new();
-        let resp = client
-            .get(&url)
-            .basic_auth(&self.access_key, Some(&self.secret_key))
-            .send()
-            .await?;
-
-        if !resp.status().is_success() {
-            anyhow::bail!("Failed to list objects: {}", resp.status());
-        }
-
-        let body = resp.text().await?;
+        let client = self.s3_client();
         let mut objects = Vec::new();
-
-        for line in body.lines() {
-            if let Some(start) = line.find("<Key>") {
-                if let Some(end) = line.find("</Key>") {
-                    let key = &line[start + 5..end];
-                    objects.push(key.to_string());
-                }
+        let mut continuation = None;
+        loop {
+            let output = client
+                .list_objects_v2()
+                .bucket(bucket)
+                .set_prefix(prefix.map(str::to_string))
+                .set_continuation_token(continuation)
+                .send()
+                .await
+                .with_context(|| format!("Failed to list objects in '{bucket}'"))?;
+
+            objects.extend(
+                output
+                    .contents()
+                    .iter()
+                    .filter_map(|object| object.key().map(str::to_string)),
+            );
+
+            match output.next_continuation_token() {
+                Some(token) => continuation = Some(token.to_string()),
+                None => return Ok(objects),
             }
         }
-
-        Ok(objects)
     }
 
     pub async fn bucket_exists(&self, name: &str) -> Result<bool> {
-        let url = format!("{}/{}", self.endpoint(), name);
-        let client = reqwest::Client::new();
-        let resp = client
-            .head(&url)
-            .basic_auth(&self.access_key, Some(&self.secret_key))
-            .send()
-            .await?;
-
-        Ok(resp.status().is_success())
+        match self.s3_client().head_bucket().bucket(name).send().await {
+            Ok(_) => Ok(true),
+            Err(e)
+                if e.as_service_error()
+                    .is_some_and(HeadBucketError::is_not_found) =>
+            {
+                Ok(false)
+            }
+            Err(e) => Err(e).with_context(|| format!("Failed to check bucket '{name}'")),
+        }
     }
 
     pub async fn delete_bucket(&self, name: &str) -> Result<()> {
         log::info!("Deleting bucket '{name}'");
 
-        let url = format!("{}/{}", self.endpoint(), name);
-        let client = reqwest::Client::new();
-        let resp = client
-            .delete(&url)
-            .basic_auth(&self.access_key, Some(&self.secret_key))
-            .send()
-            .await?;
-
-        if !resp.status().is_success() && resp.status().as_u16() != 404 {
-            anyhow::bail!("Failed to delete bucket: {}", resp.status());
+        match self.s3_client().delete_bucket().bucket(name).send().await {
+            Ok(_) => Ok(()),
+            Err(e) if e.raw_response().is_some_and(|r| r.status().as_u16() == 404) => Ok(()),
+            Err(e) => Err(e).with_context(|| format!("Failed to delete bucket '{name}'")),
         }
-
-        Ok(())
     }
 
     #[must_use]
@@ -374,24 +349,11 @@ impl MinioService {
         config.insert("endpoint_url".to_string(), self.endpoint());
         config.insert("access_key_id".to_string(), self.access_key.clone());
         config.insert("secret_access_key".to_string(), self.secret_key.clone());
-        config.insert("region".to_string(), "us-east-1".to_string());
+        config.insert("region".to_string(), Self::REGION.to_string());
         config.insert("force_path_style".to_string(), "true".to_string());
         config
     }
 
-    fn find_mc_binary() -> Result<PathBuf> {
-        let common_paths = ["/usr/local/bin/mc", "/usr/bin/mc", "/opt/homebrew/bin/mc"];
-
-        for path in common_paths {
-            let p = PathBuf::from(path);
-            if p.exists() {
-                return Ok(p);
-            }
-        }
-
-        which::which("mc").context("mc binary not found")
-    }
-
     pub async fn stop(&mut self) -> Result<()> {
         if let Some(ref mut child) = self.process {
             log::info!("Stopping MinIO...");
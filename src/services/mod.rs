@@ -1,15 +1,17 @@
 
+mod api_client;
 mod browser_service;
 mod minio;
 mod postgres;
 mod redis;
 
+pub use api_client::ApiClient;
 pub use browser_service::{BrowserService, DEFAULT_DEBUG_PORT};
 pub use minio::MinioService;
-pub use postgres::PostgresService;
+pub use postgres::{PostgresService, SnapshotHandle};
 pub use redis::RedisService;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::path::Path;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -33,10 +35,75 @@ where
     anyhow::bail!("Timeout waiting for condition")
 }
 
+pub const STARTUP_RETRY_ATTEMPTS: u32 = 3;
+
+pub const STARTUP_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+pub async fn retry_with_backoff<T, F, Fut>(
+    attempts: u32,
+    initial_backoff: Duration,
+    mut operation: F,
+) -> Result<T>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let attempts = attempts.max(1);
+    let mut backoff = initial_backoff;
+    let mut attempt = 1;
+    loop {
+        match operation(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < attempts => {
+                log::warn!(
+                    "Attempt {attempt}/{attempts} failed, retrying in {}ms: {e:#}",
+                    backoff.as_millis()
+                );
+                sleep(backoff).await;
+                backoff = backoff.saturating_mul(2);
+                attempt += 1;
+            }
+            Err(e) => return Err(e.context(format!("Giving up after {attempts} attempts"))),
+        }
+    }
+}
+
 pub async fn check_tcp_port(host: &str, port: u16) -> bool {
     tokio::net::TcpStream::connect((host, port)).await.is_ok()
 }
 
+pub const HTTP_HEALTH_INTERVAL: Duration = Duration::from_millis(250);
+
+pub async fn wait_for_http_health(
+    url: &str,
+    timeout: Duration,
+    accept_invalid_certs: bool,
+) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(accept_invalid_certs)
+        .timeout(timeout.min(Duration::from_secs(5)))
+        .build()
+        .context("Failed to build health check client")?;
+    let health_url = format!("{}/health", url.trim_end_matches('/'));
+    let is_success = |resp: reqwest::Response| resp.status().is_success();
+
+    let start = std::time::Instant::now();
+    let mut next_report = Duration::from_secs(10);
+    while start.elapsed() < timeout {
+        if client.get(&health_url).send().await.is_ok_and(is_success)
+            || client.get(url).send().await.is_ok_and(is_success)
+        {
+            return Ok(());
+        }
+        if start.elapsed() >= next_report {
+            log::info!("Still waiting for {url}... ({}s)", next_report.as_secs());
+            next_report += Duration::from_secs(10);
+        }
+        sleep(HTTP_HEALTH_INTERVAL).await;
+    }
+    anyhow::bail!("{url} did not become healthy within {}s", timeout.as_secs())
+}
+
 pub fn ensure_dir(path: &Path) -> Result<()> {
     if !path.exists() {
         std::fs::create_dir_all(path)?;
@@ -89,4 +156,83 @@ mod tests {
         .await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_recovers_from_transient_bind_failure() {
+        let blocker = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = blocker.local_addr().unwrap().port();
+        let release = tokio::spawn(async move {
+            sleep(Duration::from_millis(150)).await;
+            drop(blocker);
+        });
+
+        let mut tried = Vec::new();
+        let listener = retry_with_backoff(5, Duration::from_millis(100), |attempt| {
+            tried.push(attempt);
+            async move {
+                std::net::TcpListener::bind(("127.0.0.1", port))
+                    .with_context(|| format!("Port {port} still in use"))
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(listener.local_addr().unwrap().port(), port);
+        assert!(tried.len() > 1);
+        release.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_reports_last_error() {
+        let mut calls = 0;
+        let err = retry_with_backoff::<(), _, _>(3, Duration::from_millis(1), |attempt| {
+            calls += 1;
+            async move { anyhow::bail!("bind failed on attempt {attempt}") }
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(calls, 3);
+        assert_eq!(err.to_string(), "Giving up after 3 attempts");
+        assert!(format!("{err:#}").contains("bind failed on attempt 3"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_http_health_after_delay() {
+        let port = crate::ports::PortAllocator::allocate();
+        let url = format!("http://127.0.0.1:{port}");
+
+        let server = tokio::spawn(async move {
+            sleep(Duration::from_millis(500)).await;
+            let listener = std::net::TcpListener::bind(("127.0.0.1", port)).unwrap();
+            let server = wiremock::MockServer::builder()
+                .listener(listener)
+                .start()
+                .await;
+            wiremock::Mock::given(wiremock::matchers::path("/health"))
+                .respond_with(wiremock::ResponseTemplate::new(200))
+                .mount(&server)
+                .await;
+            sleep(Duration::from_secs(5)).await;
+        });
+
+        let start = std::time::Instant::now();
+        wait_for_http_health(&url, Duration::from_secs(5), false)
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(500));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_http_health_times_out() {
+        let port = crate::ports::PortAllocator::allocate();
+        let url = format!("http://127.0.0.1:{port}");
+
+        let err = wait_for_http_health(&url, Duration::from_millis(600), true)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("did not become healthy"));
+    }
 }
@@ -1,10 +1,11 @@
 use super::{check_tcp_port, ensure_dir, wait_for, HEALTH_CHECK_INTERVAL, HEALTH_CHECK_TIMEOUT};
+use crate::error::{ensure_port_free, HarnessError};
 use anyhow::{Context, Result};
 #[cfg(unix)]
 use nix::sys::signal::{kill, Signal};
 #[cfg(unix)]
 use nix::unistd::Pid;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::time::Duration;
 use tokio::time::sleep;
@@ -17,24 +18,26 @@ pub struct RedisService {
 }
 
 impl RedisService {
-    pub async fn start(port: u16, data_dir: &str) -> Result<Self> {
-        let data_path = PathBuf::from(data_dir).join("redis");
-        ensure_dir(&data_path)?;
-
-        let mut service = Self {
-            port,
-            data_dir: data_path,
-            process: None,
-            password: None,
-        };
-
-        service.start_server().await?;
-        service.wait_ready().await?;
+    pub async fn start(port: u16, data_dir: &str) -> Result<Self, HarnessError> {
+        Self::start_internal(port, data_dir, None).await
+    }
 
-        Ok(service)
+    pub async fn start_with_password(
+        port: u16,
+        data_dir: &str,
+        password: &str,
+    ) -> Result<Self, HarnessError> {
+        Self::start_internal(port, data_dir, Some(password.to_string())).await
     }
 
-    pub async fn start_with_password(port: u16, data_dir: &str, password: &str) -> Result<Self> {
+    async fn start_internal(
+        port: u16,
+        data_dir: &str,
+        password: Option<String>,
+    ) -> Result<Self, HarnessError> {
+        let redis = Self::find_binary().map_err(|e| HarnessError::binary_not_found("Redis", &e))?;
+        ensure_port_free("Redis", port)?;
+
         let data_path = PathBuf::from(data_dir).join("redis");
         ensure_dir(&data_path)?;
 
@@ -42,21 +45,25 @@ impl RedisService {
             port,
             data_dir: data_path,
             process: None,
-            password: Some(password.to_string()),
+            password,
         };
 
-        service.start_server().await?;
-        service.wait_ready().await?;
+        service.start_server(&redis).await?;
+        service
+            .wait_ready()
+            .await
+            .map_err(|_| HarnessError::HealthTimeout {
+                service: "Redis",
+                timeout: HEALTH_CHECK_TIMEOUT,
+            })?;
 
         Ok(service)
     }
 
-    async fn start_server(&mut self) -> Result<()> {
+    async fn start_server(&mut self, redis: &Path) -> Result<()> {
         tokio::task::yield_now().await;
         log::info!("Starting Redis on port {}", self.port);
 
-        let redis = Self::find_binary()?;
-
         let mut args = vec![
             "--port".to_string(),
             self.port.to_string(),
@@ -81,7 +88,7 @@ impl RedisService {
             args.push(password.clone());
         }
 
-        let child = Command::new(&redis)
+        let child = Command::new(redis)
             .args(&args)
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -197,6 +204,23 @@ impl RedisService {
         Ok(())
     }
 
+    pub async fn seed(&self, entries: &[(&str, &str)]) -> Result<()> {
+        for (key, value) in entries {
+            self.set(key, value).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn assert_key_eq(&self, key: &str, expected: &str) -> Result<()> {
+        match self.get(key).await? {
+            Some(value) if value == expected => Ok(()),
+            Some(value) => {
+                anyhow::bail!("Redis key '{key}' expected '{expected}' but was '{value}'")
+            }
+            None => anyhow::bail!("Redis key '{key}' expected '{expected}' but was missing"),
+        }
+    }
+
     pub async fn publish(&self, channel: &str, message: &str) -> Result<i64> {
         let result = self.execute(&["PUBLISH", channel, message]).await?;
         let count = result.replace("(integer) ", "").parse::<i64>().unwrap_or(0);
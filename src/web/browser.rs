@@ -5,7 +5,7 @@ use chromiumoxide::page::Page;
 use chromiumoxide::Element as CdpElement;
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
@@ -24,7 +24,6 @@ pub enum BrowserType {
     Edge,
 }
 
-
 impl BrowserType {
     #[must_use]
     pub const fn browser_name(self) -> &'static str {
@@ -56,6 +55,11 @@ pub struct BrowserConfig {
     pub window_height: u32,
     pub timeout: Duration,
     pub binary_path: Option<String>,
+    pub user_data_dir: Option<PathBuf>,
+    pub reuse_profile: bool,
+    pub proxy: Option<String>,
+    pub proxy_bypass_list: Vec<String>,
+    pub app_ready_expression: String,
 }
 
 impl Default for BrowserConfig {
@@ -72,11 +76,18 @@ impl Default for BrowserConfig {
             window_height: 1080,
             timeout: Duration::from_secs(30),
             binary_path,
+            user_data_dir: None,
+            reuse_profile: false,
+            proxy: None,
+            proxy_bypass_list: Vec::new(),
+            app_ready_expression: Self::DEFAULT_APP_READY_EXPRESSION.to_string(),
         }
     }
 }
 
 impl BrowserConfig {
+    pub const DEFAULT_APP_READY_EXPRESSION: &'static str = "window.__APP_READY__ === true";
+
     fn detect_browser_binary() -> Option<String> {
         if let Ok(path) = std::env::var("BROWSER_BINARY") {
             if std::path::Path::new(&path).exists() {
@@ -183,6 +194,51 @@ impl BrowserConfig {
         self
     }
 
+    #[must_use]
+    pub fn with_user_data_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.user_data_dir = Some(path.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn reuse_profile(mut self, reuse: bool) -> Self {
+        self.reuse_profile = reuse;
+        self
+    }
+
+    #[must_use]
+    pub fn with_proxy(mut self, url: &str) -> Self {
+        self.proxy = Some(url.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn with_proxy_bypass(mut self, hosts: &[&str]) -> Self {
+        self.proxy_bypass_list
+            .extend(hosts.iter().map(|h| (*h).to_string()));
+        self
+    }
+
+    #[must_use]
+    pub fn with_app_ready_expression(mut self, expression: &str) -> Self {
+        self.app_ready_expression = expression.to_string();
+        self
+    }
+
+    fn proxy_args(&self) -> Vec<String> {
+        let Some(ref proxy) = self.proxy else {
+            return Vec::new();
+        };
+        let mut args = vec![format!("--proxy-server={proxy}")];
+        if !self.proxy_bypass_list.is_empty() {
+            args.push(format!(
+                "--proxy-bypass-list={}",
+                self.proxy_bypass_list.join(";")
+            ));
+        }
+        args
+    }
+
     pub fn build_cdp_config(&self) -> Result<CdpBrowserConfig> {
         let mut builder = CdpBrowserConfig::builder();
 
@@ -190,6 +246,10 @@ impl BrowserConfig {
             builder = builder.chrome_executable(binary);
         }
 
+        if let Some(ref dir) = self.user_data_dir {
+            builder = builder.user_data_dir(dir);
+        }
+
         if self.headless {
             builder = builder.arg("--headless=new");
         }
@@ -204,6 +264,10 @@ impl BrowserConfig {
             ))
             .port(self.debug_port);
 
+        for arg in self.proxy_args() {
+            builder = builder.arg(arg);
+        }
+
         builder
             .build()
             .map_err(|e| anyhow::anyhow!("Failed to build CDP browser config: {e}"))
@@ -218,15 +282,81 @@ impl BrowserConfig {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestLogEntry {
+    pub method: String,
+    pub url: String,
+    pub status: Option<u16>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsoleLevel {
+    Debug,
+    Log,
+    Info,
+    Warning,
+    Error,
+}
+
+impl ConsoleLevel {
+    #[must_use]
+    pub fn from_cdp(kind: &str) -> Self {
+        match kind {
+            "error" | "assert" => Self::Error,
+            "warning" => Self::Warning,
+            "info" => Self::Info,
+            "debug" | "trace" => Self::Debug,
+            _ => Self::Log,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsoleMessage {
+    pub level: ConsoleLevel,
+    pub text: String,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogAction {
+    Accept,
+    Dismiss,
+    Respond(String),
+}
+
 pub struct Browser {
     cdp: Arc<CdpBrowser>,
     page: Arc<Mutex<Page>>,
     config: BrowserConfig,
-    _handle: tokio::task::JoinHandle<()>,
+    handler: tokio::task::JoinHandle<()>,
+    launched: bool,
+    listeners: std::sync::Mutex<Vec<tokio::task::JoinHandle<()>>>,
+    request_log: Arc<std::sync::Mutex<Vec<RequestLogEntry>>>,
+    console_messages: Arc<std::sync::Mutex<Vec<ConsoleMessage>>>,
+    dialog_action: Arc<std::sync::Mutex<Option<DialogAction>>>,
+    frames: std::sync::Mutex<Vec<Locator>>,
 }
 
 impl Browser {
+    pub const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
     pub async fn new(config: BrowserConfig) -> Result<Self> {
+        let timeout = config.timeout;
+        let port = config.debug_port;
+
+        tokio::time::timeout(timeout, Self::connect(config))
+            .await
+            .unwrap_or_else(|_| {
+                Err(anyhow::anyhow!(
+                    "Timed out after {}ms connecting to browser CDP on port {port}",
+                    timeout.as_millis()
+                ))
+            })
+    }
+
+    async fn connect(config: BrowserConfig) -> Result<Self> {
         log::info!("Connecting to browser CDP on port {}", config.debug_port);
 
         let json_url = format!("http://127.0.0.1:{}/json/version", config.debug_port);
@@ -317,7 +447,13 @@ impl Browser {
             cdp: Arc::new(browser),
             page: Arc::new(Mutex::new(page)),
             config,
-            _handle: handle,
+            handler: handle,
+            launched: false,
+            listeners: std::sync::Mutex::new(Vec::new()),
+            request_log: Arc::new(std::sync::Mutex::new(Vec::new())),
+            console_messages: Arc::new(std::sync::Mutex::new(Vec::new())),
+            dialog_action: Arc::new(std::sync::Mutex::new(None)),
+            frames: std::sync::Mutex::new(Vec::new()),
         })
     }
 
@@ -376,7 +512,13 @@ impl Browser {
             cdp: Arc::new(browser),
             page: Arc::new(Mutex::new(page)),
             config,
-            _handle: handle,
+            handler: handle,
+            launched: true,
+            listeners: std::sync::Mutex::new(Vec::new()),
+            request_log: Arc::new(std::sync::Mutex::new(Vec::new())),
+            console_messages: Arc::new(std::sync::Mutex::new(Vec::new())),
+            dialog_action: Arc::new(std::sync::Mutex::new(None)),
+            frames: std::sync::Mutex::new(Vec::new()),
         })
     }
 
@@ -389,6 +531,7 @@ impl Browser {
     }
 
     pub async fn goto(&self, url: &str) -> Result<()> {
+        self.switch_to_default_content()?;
         if url.starts_with("https://") {
             log::info!("Using JavaScript navigation for HTTPS URL: {url}");
 
@@ -438,6 +581,243 @@ impl Browser {
         Ok(())
     }
 
+    pub async fn enable_request_logging(&self) -> Result<()> {
+        use chromiumoxide::cdp::browser_protocol::network::{
+            EnableParams, EventRequestWillBeSent, EventResponseReceived,
+        };
+
+        enum NetworkEvent {
+            Request(Arc<EventRequestWillBeSent>),
+            Response(Arc<EventResponseReceived>),
+        }
+
+        let (requests, responses) = {
+            let page = self.page.lock().await;
+            page.execute(EnableParams::default())
+                .await
+                .context("Failed to enable CDP network events")?;
+            let requests = page
+                .event_listener::<EventRequestWillBeSent>()
+                .await
+                .context("Failed to subscribe to network requests")?;
+            let responses = page
+                .event_listener::<EventResponseReceived>()
+                .await
+                .context("Failed to subscribe to network responses")?;
+            (requests, responses)
+        };
+
+        let log = self.request_log.clone();
+        let mut events = futures::stream::select(
+            requests.map(NetworkEvent::Request),
+            responses.map(NetworkEvent::Response),
+        );
+        let listener = tokio::spawn(async move {
+            let mut pending = std::collections::HashMap::new();
+            while let Some(event) = events.next().await {
+                let Ok(mut entries) = log.lock() else {
+                    break;
+                };
+                match event {
+                    NetworkEvent::Request(req) => {
+                        pending.insert(req.request_id.inner().clone(), entries.len());
+                        entries.push(RequestLogEntry {
+                            method: req.request.method.clone(),
+                            url: req.request.url.clone(),
+                            status: None,
+                        });
+                    }
+                    NetworkEvent::Response(resp) => {
+                        if let Some(&index) = pending.get(resp.request_id.inner()) {
+                            entries[index].status = u16::try_from(resp.response.status).ok();
+                        }
+                    }
+                }
+            }
+        });
+
+        if let Ok(mut listeners) = self.listeners.lock() {
+            listeners.push(listener);
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn request_log(&self) -> Vec<RequestLogEntry> {
+        self.request_log
+            .lock()
+            .map(|log| log.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn clear_request_log(&self) {
+        if let Ok(mut log) = self.request_log.lock() {
+            log.clear();
+        }
+    }
+
+    pub async fn enable_console_capture(&self) -> Result<()> {
+        use chromiumoxide::cdp::js_protocol::runtime::{
+            EnableParams, EventConsoleApiCalled, EventExceptionThrown, RemoteObject,
+        };
+
+        enum RuntimeEvent {
+            Console(Arc<EventConsoleApiCalled>),
+            Exception(Arc<EventExceptionThrown>),
+        }
+
+        fn describe(object: &RemoteObject) -> String {
+            match &object.value {
+                Some(serde_json::Value::String(text)) => text.clone(),
+                Some(value) => value.to_string(),
+                None => object.description.clone().unwrap_or_default(),
+            }
+        }
+
+        let (console, exceptions) = {
+            let page = self.page.lock().await;
+            page.execute(EnableParams::default())
+                .await
+                .context("Failed to enable CDP runtime events")?;
+            let console = page
+                .event_listener::<EventConsoleApiCalled>()
+                .await
+                .context("Failed to subscribe to console messages")?;
+            let exceptions = page
+                .event_listener::<EventExceptionThrown>()
+                .await
+                .context("Failed to subscribe to exceptions")?;
+            (console, exceptions)
+        };
+
+        let messages = self.console_messages.clone();
+        let mut events = futures::stream::select(
+            console.map(RuntimeEvent::Console),
+            exceptions.map(RuntimeEvent::Exception),
+        );
+        let listener = tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                let message = match event {
+                    RuntimeEvent::Console(call) => ConsoleMessage {
+                        level: ConsoleLevel::from_cdp(call.r#type.as_ref()),
+                        text: call.args.iter().map(describe).collect::<Vec<_>>().join(" "),
+                        source: call
+                            .stack_trace
+                            .as_ref()
+                            .and_then(|trace| trace.call_frames.first())
+                            .map(|frame| format!("{}:{}", frame.url, frame.line_number + 1)),
+                    },
+                    RuntimeEvent::Exception(thrown) => {
+                        let details = &thrown.exception_details;
+                        ConsoleMessage {
+                            level: ConsoleLevel::Error,
+                            text: details
+                                .exception
+                                .as_ref()
+                                .map_or_else(|| details.text.clone(), describe),
+                            source: details
+                                .url
+                                .as_ref()
+                                .map(|url| format!("{url}:{}", details.line_number + 1)),
+                        }
+                    }
+                };
+                let Ok(mut entries) = messages.lock() else {
+                    break;
+                };
+                entries.push(message);
+            }
+        });
+
+        if let Ok(mut listeners) = self.listeners.lock() {
+            listeners.push(listener);
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn console_messages(&self) -> Vec<ConsoleMessage> {
+        self.console_messages
+            .lock()
+            .map(|messages| messages.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn clear_console_messages(&self) {
+        if let Ok(mut messages) = self.console_messages.lock() {
+            messages.clear();
+        }
+    }
+
+    pub fn assert_no_console_errors(&self) -> Result<()> {
+        let errors: Vec<String> = self
+            .console_messages()
+            .into_iter()
+            .filter(|m| m.level == ConsoleLevel::Error)
+            .map(|m| match m.source {
+                Some(source) => format!("{} ({source})", m.text),
+                None => m.text,
+            })
+            .collect();
+        if !errors.is_empty() {
+            anyhow::bail!("Console errors: {}", errors.join("; "));
+        }
+        Ok(())
+    }
+
+    pub async fn on_dialog(&self, action: DialogAction) -> Result<()> {
+        use chromiumoxide::cdp::browser_protocol::page::{
+            EventJavascriptDialogOpening, HandleJavaScriptDialogParams,
+        };
+
+        let already_listening = self
+            .dialog_action
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Dialog action lock poisoned"))?
+            .replace(action)
+            .is_some();
+        if already_listening {
+            return Ok(());
+        }
+
+        let page = self.page.lock().await.clone();
+        let mut dialogs = page
+            .event_listener::<EventJavascriptDialogOpening>()
+            .await
+            .context("Failed to subscribe to JavaScript dialogs")?;
+
+        let action = self.dialog_action.clone();
+        let listener = tokio::spawn(async move {
+            while let Some(dialog) = dialogs.next().await {
+                let Some(current) = action.lock().ok().and_then(|a| a.clone()) else {
+                    continue;
+                };
+                log::debug!(
+                    "Handling {:?} dialog '{}' with {current:?}",
+                    dialog.r#type,
+                    dialog.message
+                );
+                let builder = match current {
+                    DialogAction::Accept => HandleJavaScriptDialogParams::builder().accept(true),
+                    DialogAction::Dismiss => HandleJavaScriptDialogParams::builder().accept(false),
+                    DialogAction::Respond(text) => HandleJavaScriptDialogParams::builder()
+                        .accept(true)
+                        .prompt_text(text),
+                };
+                if let Ok(cmd) = builder.build() {
+                    if let Err(e) = page.execute(cmd).await {
+                        log::warn!("Failed to handle JavaScript dialog: {e}");
+                    }
+                }
+            }
+        });
+
+        if let Ok(mut listeners) = self.listeners.lock() {
+            listeners.push(listener);
+        }
+        Ok(())
+    }
+
     pub async fn current_url(&self) -> Result<String> {
         let url = {
             let page = self.page.lock().await;
@@ -469,13 +849,24 @@ impl Browser {
     }
 
     pub async fn find(&self, locator: Locator) -> Result<Element> {
-        let element = {
-            let page = self.page.lock().await;
-            let selector = locator.to_css_selector();
-            page.find_element(&selector)
-                .await
-                .context(format!("Failed to find element: {locator:?}"))?
-        };
+        if let Locator::Any(candidates) = &locator {
+            for candidate in candidates {
+                if let Ok(found) = Box::pin(self.find(candidate.clone())).await {
+                    return Ok(Element {
+                        inner: found.inner,
+                        locator,
+                    });
+                }
+            }
+            anyhow::bail!("Failed to find element: {locator:?}");
+        }
+
+        let element = self
+            .query_elements(&locator)
+            .await?
+            .into_iter()
+            .next()
+            .with_context(|| format!("Failed to find element: {locator:?}"))?;
 
         Ok(Element {
             inner: element,
@@ -483,15 +874,53 @@ impl Browser {
         })
     }
 
-    pub async fn find_all(&self, locator: Locator) -> Result<Vec<Element>> {
-        let elements = {
+    async fn resolve_selector(&self, locator: &Locator) -> Result<String> {
+        if let Some(selector) = locator.to_css_selector() {
+            return Ok(selector);
+        }
+        let marker = uuid::Uuid::new_v4().simple().to_string();
+        self.execute_script(&locator.match_script("document", &marker))
+            .await?;
+        Ok(format!("[data-bottest-match='{marker}']"))
+    }
+
+    async fn query_elements(&self, locator: &Locator) -> Result<Vec<CdpElement>> {
+        let Some(document) = self.frame_document() else {
+            let selector = self.resolve_selector(locator).await?;
             let page = self.page.lock().await;
-            let selector = locator.to_css_selector();
-            page.find_elements(&selector)
+            return page
+                .find_elements(&selector)
                 .await
-                .context(format!("Failed to find elements: {locator:?}"))?
+                .context(format!("Failed to find elements: {locator:?}"));
         };
 
+        let marker = uuid::Uuid::new_v4().simple().to_string();
+        let matched = self
+            .execute_script(&locator.match_script(&document, &marker))
+            .await
+            .context("Selected frame is no longer accessible")?;
+        if matched.as_u64().unwrap_or(0) == 0 {
+            return Ok(Vec::new());
+        }
+        let page = self.page.lock().await;
+        page.find_xpaths(format!("[data-bottest-match='{marker}']"))
+            .await
+            .context(format!("Failed to find elements in frame: {locator:?}"))
+    }
+
+    fn frame_document(&self) -> Option<String> {
+        let frames = self.frames.lock().ok()?;
+        if frames.is_empty() {
+            return None;
+        }
+        Some(frames.iter().fold("document".to_string(), |root, frame| {
+            format!("({}.contentDocument)", frame.element_expression(&root))
+        }))
+    }
+
+    pub async fn find_all(&self, locator: Locator) -> Result<Vec<Element>> {
+        let elements = self.query_elements(&locator).await?;
+
         Ok(elements
             .into_iter()
             .map(|e| Element {
@@ -564,6 +993,33 @@ impl Browser {
         anyhow::bail!("Timeout waiting for element {locator:?} with condition {condition:?}")
     }
 
+    pub async fn wait_for_app_ready(&self, flag_expr: &str, timeout: Duration) -> Result<()> {
+        let script = format!(
+            "(() => {{ try {{ return Boolean({flag_expr}); }} catch (_) {{ return false; }} }})()"
+        );
+        let start = std::time::Instant::now();
+
+        loop {
+            if self
+                .execute_script(&script)
+                .await?
+                .as_bool()
+                .unwrap_or(false)
+            {
+                return Ok(());
+            }
+            if start.elapsed() >= timeout {
+                anyhow::bail!("App not ready: `{flag_expr}` still false after {timeout:?}");
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    pub async fn wait_for_default_app_ready(&self) -> Result<()> {
+        self.wait_for_app_ready(&self.config.app_ready_expression, self.config.timeout)
+            .await
+    }
+
     pub async fn click(&self, locator: Locator) -> Result<()> {
         let elem = self
             .wait_for_condition(locator, WaitCondition::Clickable)
@@ -579,6 +1035,93 @@ impl Browser {
         elem.send_keys(text).await
     }
 
+    pub async fn fill_form(&self, fields: &[(Locator, &str)]) -> Result<()> {
+        for (index, (locator, value)) in fields.iter().enumerate() {
+            self.fill(locator.clone(), value)
+                .await
+                .with_context(|| format!("Failed to fill form field #{index} ({locator:?})"))?;
+        }
+        Ok(())
+    }
+
+    pub async fn submit_form(&self, fields: &[(Locator, &str)], submit: Locator) -> Result<()> {
+        self.fill_form(fields).await?;
+        self.click(submit.clone())
+            .await
+            .with_context(|| format!("Failed to submit form via {submit:?}"))
+    }
+
+    pub async fn select_by_value(&self, locator: Locator, value: &str) -> Result<()> {
+        self.select_option(locator, "o.value", value).await
+    }
+
+    pub async fn select_by_text(&self, locator: Locator, text: &str) -> Result<()> {
+        self.select_option(locator, "o.text.trim()", text.trim())
+            .await
+    }
+
+    pub async fn selected_option(&self, locator: Locator) -> Result<Option<String>> {
+        let elem = self.find(locator).await?;
+        let value = elem
+            .call_function(
+                "function() { const option = this.options && this.options[this.selectedIndex]; \
+                 return option ? option.value : null; }",
+            )
+            .await?;
+        Ok(value.as_str().map(str::to_string))
+    }
+
+    async fn select_option(&self, locator: Locator, field: &str, wanted: &str) -> Result<()> {
+        let elem = self
+            .wait_for_condition(locator.clone(), WaitCondition::Visible)
+            .await?;
+        let wanted_js = serde_json::to_string(wanted)?;
+        let selected = elem
+            .call_bool(&format!(
+                "function() {{ const option = Array.from(this.options || []).find(o => {field} === {wanted_js}); \
+                 if (!option) return false; \
+                 this.value = option.value; \
+                 this.dispatchEvent(new Event('input', {{ bubbles: true }})); \
+                 this.dispatchEvent(new Event('change', {{ bubbles: true }})); \
+                 return true; }}"
+            ))
+            .await?;
+        if !selected {
+            anyhow::bail!("No option matching {wanted:?} in {locator:?}");
+        }
+        Ok(())
+    }
+
+    pub async fn drag_file(&self, target: Locator, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .with_context(|| format!("Invalid file name: {}", path.display()))?;
+
+        let elem = self
+            .wait_for_condition(target.clone(), WaitCondition::Visible)
+            .await?;
+        let script = format!(
+            "function() {{ const file = new File([new Uint8Array({bytes})], {name}, {{ type: {mime} }}); \
+             const data = new DataTransfer(); \
+             data.items.add(file); \
+             for (const type of ['dragenter', 'dragover', 'drop']) {{ \
+               this.dispatchEvent(new DragEvent(type, {{ bubbles: true, cancelable: true, dataTransfer: data }})); \
+             }} \
+             return true; }}",
+            bytes = serde_json::to_string(&bytes)?,
+            name = serde_json::to_string(name)?,
+            mime = serde_json::to_string(mime_type_for(path))?,
+        );
+        elem.call_function(&script)
+            .await
+            .with_context(|| format!("Failed to drop {} onto {target:?}", path.display()))?;
+        Ok(())
+    }
+
     pub async fn text(&self, locator: Locator) -> Result<String> {
         let elem = self.find(locator).await?;
         elem.text().await
@@ -759,7 +1302,41 @@ impl Browser {
     }
 
     pub fn close(self) -> Result<()> {
-        let _ = self.cdp;
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn(async move {
+                    if let Err(e) = self.shutdown().await {
+                        log::warn!("Browser shutdown failed: {e:?}");
+                    }
+                });
+            }
+            Err(_) => drop(self),
+        }
+        Ok(())
+    }
+
+    pub async fn shutdown(mut self) -> Result<()> {
+        if let Some(cdp) = Arc::get_mut(&mut self.cdp) {
+            let mut exited = false;
+            if self.launched {
+                let _ = cdp.close().await;
+                exited = tokio::time::timeout(Self::SHUTDOWN_GRACE, cdp.wait())
+                    .await
+                    .is_ok_and(|status| status.is_ok());
+            }
+            if !exited {
+                if let Some(result) = cdp.kill().await {
+                    result.context("Failed to kill browser process")?;
+                    cdp.wait().await.context("Failed to reap browser process")?;
+                }
+            }
+        }
+
+        if !self.config.reuse_profile {
+            if let Some(ref dir) = self.config.user_data_dir {
+                let _ = std::fs::remove_dir_all(dir);
+            }
+        }
         Ok(())
     }
 
@@ -815,23 +1392,39 @@ impl Browser {
         }
     }
 
-    pub fn switch_to_frame(&self, _locator: Locator) -> Result<()> {
-        let _ = &self.page;
+    pub async fn switch_to_frame(&self, locator: Locator) -> Result<()> {
+        let root = self
+            .frame_document()
+            .unwrap_or_else(|| "document".to_string());
+        let frame = locator.element_expression(&root);
+        let accessible = self
+            .execute_script(&format!("(() => !!{frame}?.contentDocument)()"))
+            .await?;
+        if accessible != serde_json::Value::Bool(true) {
+            anyhow::bail!("Frame {locator:?} not found or not accessible");
+        }
+        if let Ok(mut frames) = self.frames.lock() {
+            frames.push(locator);
+        }
         Ok(())
     }
 
-    pub fn switch_to_frame_by_index(&self, _index: u16) -> Result<()> {
-        let _ = &self.page;
-        Ok(())
+    pub async fn switch_to_frame_by_index(&self, index: u16) -> Result<()> {
+        self.switch_to_frame(Locator::nth("iframe, frame", usize::from(index)))
+            .await
     }
 
     pub fn switch_to_parent_frame(&self) -> Result<()> {
-        let _ = &self.page;
+        if let Ok(mut frames) = self.frames.lock() {
+            frames.pop();
+        }
         Ok(())
     }
 
     pub fn switch_to_default_content(&self) -> Result<()> {
-        let _ = &self.page;
+        if let Ok(mut frames) = self.frames.lock() {
+            frames.clear();
+        }
         Ok(())
     }
 
@@ -846,6 +1439,17 @@ impl Browser {
     }
 }
 
+impl Drop for Browser {
+    fn drop(&mut self) {
+        self.handler.abort();
+        if let Ok(listeners) = self.listeners.lock() {
+            for listener in listeners.iter() {
+                listener.abort();
+            }
+        }
+    }
+}
+
 pub struct Element {
     inner: CdpElement,
     locator: Locator,
@@ -914,17 +1518,52 @@ impl Element {
     }
 
     pub async fn is_displayed(&self) -> Result<bool> {
-        Ok(self.inner.inner_text().await.is_ok())
+        self.call_bool(
+            "function() { const style = getComputedStyle(this); const rect = this.getBoundingClientRect(); \
+             return style.display !== 'none' && style.visibility !== 'hidden' && rect.width > 0 && rect.height > 0; }",
+        )
+        .await
     }
 
     pub async fn is_enabled(&self) -> Result<bool> {
-        let disabled = self.inner.attribute("disabled").await?;
-        Ok(disabled.is_none())
+        self.call_bool("function() { return !this.disabled; }")
+            .await
     }
 
     pub async fn is_selected(&self) -> Result<bool> {
-        let checked = self.inner.attribute("checked").await?;
-        Ok(checked.is_some())
+        self.call_bool("function() { return !!(this.checked || this.selected); }")
+            .await
+    }
+
+    pub async fn value(&self) -> Result<String> {
+        let value = self
+            .call_function("function() { return this.value ?? ''; }")
+            .await?;
+        Ok(match value {
+            serde_json::Value::String(text) => text,
+            serde_json::Value::Null => String::new(),
+            other => other.to_string(),
+        })
+    }
+
+    async fn call_bool(&self, function: &str) -> Result<bool> {
+        Ok(self
+            .call_function(function)
+            .await?
+            .as_bool()
+            .unwrap_or(false))
+    }
+
+    async fn call_function(&self, function: &str) -> Result<serde_json::Value> {
+        let returns = self
+            .inner
+            .call_js_fn(function, false)
+            .await
+            .context(format!(
+                "Failed to read element state for {:?}",
+                self.locator
+            ))?;
+        Ok(returns.result.value.unwrap_or(serde_json::Value::Null))
     }
 
     pub fn tag_name(&self) -> Result<String> {
@@ -960,6 +1599,25 @@ impl Element {
     }
 }
 
+fn mime_type_for(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("txt") => "text/plain",
+        Some("md") => "text/markdown",
+        Some("csv") => "text/csv",
+        Some("json") => "application/json",
+        Some("pdf") => "application/pdf",
+        Some("docx") => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        _ => "application/octet-stream",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -978,13 +1636,407 @@ mod tests {
             .with_debug_port(9333)
             .headless(false)
             .with_window_size(1280, 720)
-            .with_timeout(Duration::from_secs(60));
+            .with_timeout(Duration::from_secs(60))
+            .with_user_data_dir("/tmp/browser-test-profile")
+            .reuse_profile(true);
 
         assert_eq!(config.debug_port, 9333);
         assert!(!config.headless);
         assert_eq!(config.window_width, 1280);
         assert_eq!(config.window_height, 720);
         assert_eq!(config.timeout, Duration::from_secs(60));
+        assert_eq!(
+            config.user_data_dir,
+            Some(PathBuf::from("/tmp/browser-test-profile"))
+        );
+        assert!(config.reuse_profile);
+        assert_eq!(
+            config.app_ready_expression,
+            BrowserConfig::DEFAULT_APP_READY_EXPRESSION
+        );
+        assert_eq!(
+            config
+                .with_app_ready_expression("window.appState === 'ready'")
+                .app_ready_expression,
+            "window.appState === 'ready'"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_browser_new_fails_on_dead_port() {
+        let port = crate::ports::PortAllocator::allocate();
+        let config = BrowserConfig::new()
+            .with_debug_port(port)
+            .with_timeout(Duration::from_secs(2));
+
+        let start = std::time::Instant::now();
+        assert!(Browser::new(config).await.is_err());
+        assert!(start.elapsed() < Duration::from_secs(3));
+    }
+
+    #[tokio::test]
+    async fn test_browser_new_times_out_on_unresponsive_port() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let config = BrowserConfig::new()
+            .with_debug_port(port)
+            .with_timeout(Duration::from_millis(500));
+
+        let start = std::time::Instant::now();
+        let err = Browser::new(config).await.err().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(3));
+        assert!(err.to_string().contains("Timed out after 500ms"));
+
+        drop(listener);
+    }
+
+    #[cfg(target_os = "linux")]
+    fn processes_with_arg(arg: &str) -> Vec<u32> {
+        std::fs::read_dir("/proc")
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+                    .filter(|pid| {
+                        std::fs::read(format!("/proc/{pid}/cmdline"))
+                            .is_ok_and(|cmdline| String::from_utf8_lossy(&cmdline).contains(arg))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn fixture_config() -> BrowserConfig {
+        BrowserConfig::new()
+            .headless(true)
+            .with_debug_port(crate::ports::PortAllocator::allocate())
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn launch_test_browser(config: BrowserConfig) -> Option<Browser> {
+        if config.binary_path.is_none() {
+            eprintln!("Skipping: no browser binary found");
+            return None;
+        }
+
+        match Browser::launch(config).await {
+            Ok(browser) => Some(browser),
+            Err(e) => {
+                eprintln!("Skipping: failed to launch browser: {e}");
+                None
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn serve_fixture(html: &str) -> wiremock::MockServer {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Type", "text/html")
+                    .set_body_string(html),
+            )
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_browser_close_leaves_no_process_on_debug_port() {
+        let port = crate::ports::PortAllocator::allocate();
+        let config = BrowserConfig::new().headless(true).with_debug_port(port);
+        let Some(browser) = launch_test_browser(config).await else {
+            return;
+        };
+        let port_arg = format!("--remote-debugging-port={port}");
+        assert!(!processes_with_arg(&port_arg).is_empty());
+
+        browser.close().unwrap();
+
+        let start = std::time::Instant::now();
+        while !processes_with_arg(&port_arg).is_empty() && start.elapsed() < Duration::from_secs(5)
+        {
+            sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(processes_with_arg(&port_arg), Vec::<u32>::new());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_user_data_dir_persists_local_storage_between_sessions() {
+        let server = serve_fixture("<html><body>profile</body></html>").await;
+
+        let profile = std::env::temp_dir().join(format!("browser-test-{}", uuid::Uuid::new_v4()));
+        let config = |reuse: bool| {
+            BrowserConfig::new()
+                .headless(true)
+                .with_debug_port(crate::ports::PortAllocator::allocate())
+                .with_user_data_dir(&profile)
+                .reuse_profile(reuse)
+        };
+
+        let Some(first) = launch_test_browser(config(true)).await else {
+            return;
+        };
+        first.goto(&server.uri()).await.unwrap();
+        first
+            .execute_script("localStorage.setItem('greeting', 'hello'); true")
+            .await
+            .unwrap();
+        first.shutdown().await.unwrap();
+        assert!(profile.exists());
+
+        let second = Browser::launch(config(false)).await.unwrap();
+        second.goto(&server.uri()).await.unwrap();
+        let stored = second
+            .execute_script("localStorage.getItem('greeting')")
+            .await
+            .unwrap();
+        second.shutdown().await.unwrap();
+
+        assert_eq!(stored, serde_json::json!("hello"));
+        assert!(!profile.exists());
+    }
+
+    #[test]
+    fn test_proxy_args_in_cdp_config() {
+        assert!(BrowserConfig::new().proxy_args().is_empty());
+
+        let config = BrowserConfig::new()
+            .with_binary("/bin/sh")
+            .with_proxy("http://127.0.0.1:8888")
+            .with_proxy_bypass(&["localhost", "*.internal"]);
+
+        assert_eq!(
+            config.proxy_args(),
+            vec![
+                "--proxy-server=http://127.0.0.1:8888",
+                "--proxy-bypass-list=localhost;*.internal",
+            ]
+        );
+
+        let cdp_config = format!("{:?}", config.build_cdp_config().unwrap());
+        assert!(cdp_config.contains("--proxy-server=http://127.0.0.1:8888"));
+        assert!(cdp_config.contains("--proxy-bypass-list=localhost;*.internal"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_request_log_records_page_fetches() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let Some(browser) = launch_test_browser(fixture_config()).await else {
+            return;
+        };
+        let server = serve_fixture(
+            "<html><body><script>\
+             fetch('/api/bots');\
+             fetch('/api/messages', {method: 'POST', body: '{}'});\
+             </script></body></html>",
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path("/api/bots"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/messages"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&server)
+            .await;
+        browser.enable_request_logging().await.unwrap();
+        browser.goto(&server.uri()).await.unwrap();
+
+        let bots = RequestLogEntry {
+            method: "GET".to_string(),
+            url: format!("{}/api/bots", server.uri()),
+            status: Some(200),
+        };
+        let messages = RequestLogEntry {
+            method: "POST".to_string(),
+            url: format!("{}/api/messages", server.uri()),
+            status: Some(201),
+        };
+        let start = std::time::Instant::now();
+        let mut log = browser.request_log();
+        while !(log.contains(&bots) && log.contains(&messages))
+            && start.elapsed() < Duration::from_secs(5)
+        {
+            sleep(Duration::from_millis(100)).await;
+            log = browser.request_log();
+        }
+        browser.shutdown().await.unwrap();
+
+        assert!(log.contains(&bots), "{log:?}");
+        assert!(log.contains(&messages), "{log:?}");
+    }
+
+    #[test]
+    fn test_console_level_from_cdp() {
+        assert_eq!(ConsoleLevel::from_cdp("error"), ConsoleLevel::Error);
+        assert_eq!(ConsoleLevel::from_cdp("assert"), ConsoleLevel::Error);
+        assert_eq!(ConsoleLevel::from_cdp("warning"), ConsoleLevel::Warning);
+        assert_eq!(ConsoleLevel::from_cdp("info"), ConsoleLevel::Info);
+        assert_eq!(ConsoleLevel::from_cdp("table"), ConsoleLevel::Log);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_console_error_is_captured() {
+        let Some(browser) = launch_test_browser(fixture_config()).await else {
+            return;
+        };
+        let server = serve_fixture(
+            "<html><body><script>console.log('ready'); console.error('boom');</script></body></html>",
+        )
+        .await;
+        browser.enable_console_capture().await.unwrap();
+        browser.goto(&server.uri()).await.unwrap();
+
+        let start = std::time::Instant::now();
+        let mut messages = browser.console_messages();
+        while !messages.iter().any(|m| m.text == "boom") && start.elapsed() < Duration::from_secs(5)
+        {
+            sleep(Duration::from_millis(100)).await;
+            messages = browser.console_messages();
+        }
+        let check = browser.assert_no_console_errors();
+        browser.shutdown().await.unwrap();
+
+        let boom = messages.iter().find(|m| m.text == "boom").unwrap();
+        assert_eq!(boom.level, ConsoleLevel::Error);
+        assert!(messages
+            .iter()
+            .any(|m| m.text == "ready" && m.level == ConsoleLevel::Log));
+        assert!(check.unwrap_err().to_string().contains("boom"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_on_dialog_applies_configured_action() {
+        let Some(browser) = launch_test_browser(fixture_config()).await else {
+            return;
+        };
+        let server = serve_fixture(
+            "<html><body>\
+             <button id='delete' onclick=\"document.getElementById('result').textContent = \
+             confirm('Delete bot?') ? 'accepted' : 'dismissed'\">Delete</button>\
+             <button id='rename' onclick=\"document.getElementById('result').textContent = \
+             prompt('New name?')\">Rename</button>\
+             <div id='result'></div></body></html>",
+        )
+        .await;
+        browser.goto(&server.uri()).await.unwrap();
+
+        let mut results = Vec::new();
+        for (action, button) in [
+            (DialogAction::Accept, "delete"),
+            (DialogAction::Dismiss, "delete"),
+            (DialogAction::Respond("support-bot".to_string()), "rename"),
+        ] {
+            browser.on_dialog(action).await.unwrap();
+            browser.click(Locator::id(button)).await.unwrap();
+            sleep(Duration::from_millis(300)).await;
+            results.push(browser.text(Locator::id("result")).await.unwrap());
+        }
+        browser.shutdown().await.unwrap();
+
+        assert_eq!(results, vec!["accepted", "dismissed", "support-bot"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_element_state_queries() {
+        let Some(browser) = launch_test_browser(fixture_config()).await else {
+            return;
+        };
+        let server = serve_fixture(
+            "<html><body>\
+             <button id='take-next' disabled>Take Next</button>\
+             <button id='save'>Save</button>\
+             <input type='checkbox' id='active' checked>\
+             <input type='checkbox' id='archived'>\
+             <input id='bot-name' value='support-bot'>\
+             <div id='hidden' style='display:none'>secret</div>\
+             </body></html>",
+        )
+        .await;
+        browser.goto(&server.uri()).await.unwrap();
+
+        let mut states = Vec::new();
+        for id in ["take-next", "save", "active", "archived", "hidden"] {
+            let element = browser.find(Locator::id(id)).await.unwrap();
+            states.push((
+                element.is_enabled().await.unwrap(),
+                element.is_selected().await.unwrap(),
+                element.is_displayed().await.unwrap(),
+            ));
+        }
+        let name = browser
+            .find(Locator::id("bot-name"))
+            .await
+            .unwrap()
+            .value()
+            .await
+            .unwrap();
+        browser.shutdown().await.unwrap();
+
+        assert_eq!(
+            states,
+            vec![
+                (false, false, true),
+                (true, false, true),
+                (true, true, true),
+                (true, false, true),
+                (true, false, false),
+            ]
+        );
+        assert_eq!(name, "support-bot");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_switch_to_frame_scopes_element_lookup() {
+        let Some(browser) = launch_test_browser(fixture_config()).await else {
+            return;
+        };
+        let server = serve_fixture(
+            "<html><body><h1 id='title'>Host</h1>\
+             <iframe id='widget' srcdoc=\"<button id='inner' \
+             onclick='this.textContent=&quot;clicked&quot;'>Start chat</button>\"></iframe>\
+             </body></html>",
+        )
+        .await;
+        browser.goto(&server.uri()).await.unwrap();
+        sleep(Duration::from_millis(300)).await;
+
+        let outside = browser.find(Locator::id("inner")).await.is_err();
+        let missing = browser.switch_to_frame(Locator::id("missing")).await;
+        browser
+            .switch_to_frame(Locator::id("widget"))
+            .await
+            .unwrap();
+        let title_in_frame = browser.exists(Locator::id("title")).await;
+        browser.click(Locator::id("inner")).await.unwrap();
+        let clicked = browser.text(Locator::id("inner")).await.unwrap();
+        browser.switch_to_default_content().unwrap();
+        let title = browser.text(Locator::id("title")).await.unwrap();
+        browser.shutdown().await.unwrap();
+
+        assert!(outside);
+        assert!(missing.is_err());
+        assert!(!title_in_frame);
+        assert_eq!(clicked, "clicked");
+        assert_eq!(title, "Host");
     }
 
     #[test]
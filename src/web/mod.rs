@@ -1,7 +1,12 @@
 pub mod browser;
 pub mod pages;
+pub mod ws;
 
-pub use browser::{Browser, BrowserConfig, BrowserType};
+pub use browser::{
+    Browser, BrowserConfig, BrowserType, ConsoleLevel, ConsoleMessage, DialogAction,
+    RequestLogEntry,
+};
+pub use ws::WsClient;
 
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -73,6 +78,10 @@ pub enum Locator {
     PartialLinkText(String),
     TagName(String),
     ClassName(String),
+    Nth(String, usize),
+    Any(Vec<Locator>),
+    Text(String, String),
+    Within(Box<Locator>, Box<Locator>),
 }
 
 impl Locator {
@@ -107,18 +116,126 @@ impl Locator {
     }
 
     #[must_use]
-    pub fn to_css_selector(&self) -> String {
+    pub fn first(selector: &str) -> Self {
+        Self::nth(selector, 0)
+    }
+
+    #[must_use]
+    pub fn nth(selector: &str, index: usize) -> Self {
+        Self::Nth(selector.to_string(), index)
+    }
+
+    #[must_use]
+    pub fn within(parent: &Self, child: &Self) -> Self {
+        Self::Within(Box::new(parent.clone()), Box::new(child.clone()))
+    }
+
+    #[must_use]
+    pub const fn any(candidates: Vec<Self>) -> Self {
+        Self::Any(candidates)
+    }
+
+    #[must_use]
+    pub fn text(tag: &str, text: &str) -> Self {
+        Self::Text(tag.to_string(), text.to_string())
+    }
+
+    #[must_use]
+    pub(crate) fn elements_script(&self, root: &str) -> String {
+        let all = |selector: &str| {
+            format!(
+                "[...{root}.querySelectorAll({})]",
+                serde_json::Value::from(selector)
+            )
+        };
         match self {
-            Self::Css(s) | Self::TagName(s) => s.clone(),
+            Self::Nth(selector, index) => {
+                format!("{}.slice({index}, {})", all(selector), index + 1)
+            }
+            Self::Text(tag, text) => format!(
+                "{}.filter(e => e.textContent.includes({}))",
+                all(tag),
+                serde_json::Value::from(text.as_str())
+            ),
+            Self::LinkText(text) => format!(
+                "{}.filter(e => e.textContent.trim() === {})",
+                all("a"),
+                serde_json::Value::from(text.trim())
+            ),
+            Self::Any(candidates) => format!(
+                "[...new Set([{}].flat())]",
+                candidates
+                    .iter()
+                    .map(|c| c.elements_script(root))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Within(parent, child) => format!(
+                "[...new Set({}.flatMap(p => {}))]",
+                parent.elements_script(root),
+                child.elements_script("p")
+            ),
+            _ => all(&self.to_css_selector().unwrap_or_default()),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn match_script(&self, root: &str, marker: &str) -> String {
+        format!(
+            "(() => {{ const matches = {elements}; matches.forEach(e => e.setAttribute('data-bottest-match', {marker})); return matches.length; }})()",
+            elements = self.elements_script(root),
+            marker = serde_json::Value::from(marker),
+        )
+    }
+
+    #[must_use]
+    pub(crate) fn element_expression(&self, root: &str) -> String {
+        match (self, self.to_css_selector()) {
+            (Self::Nth(selector, index), _) => format!(
+                "{root}.querySelectorAll({})[{index}]",
+                serde_json::Value::from(selector.as_str())
+            ),
+            (_, Some(selector)) => format!(
+                "{root}.querySelector({})",
+                serde_json::Value::from(selector)
+            ),
+            (_, None) => format!("{}[0]", self.elements_script(root)),
+        }
+    }
+
+    #[must_use]
+    pub fn to_css_selector(&self) -> Option<String> {
+        match self {
+            Self::Css(s) | Self::TagName(s) => Some(s.clone()),
             Self::XPath(_) => {
                 log::warn!("XPath locators not directly supported in CDP, use CSS selectors");
-                "*".to_string()
+                Some("*".to_string())
+            }
+            Self::Id(s) => Some(format!("#{s}")),
+            Self::Name(s) => Some(format!("[name='{s}']")),
+            Self::PartialLinkText(s) => Some(format!("a[href*='{s}']")),
+            Self::ClassName(s) => Some(format!(".{s}")),
+            Self::LinkText(_) | Self::Nth(..) | Self::Text(..) => None,
+            Self::Any(candidates) => candidates
+                .iter()
+                .map(Self::to_css_selector)
+                .collect::<Option<Vec<_>>>()
+                .map(|selectors| selectors.join(", ")),
+            Self::Within(parent, child) => {
+                let parents = parent.to_css_selector()?;
+                let children = child.to_css_selector()?;
+                Some(
+                    parents
+                        .split(',')
+                        .flat_map(|p| {
+                            children
+                                .split(',')
+                                .map(move |c| format!("{} {}", p.trim(), c.trim()))
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
             }
-            Self::Id(s) => format!("#{s}"),
-            Self::Name(s) => format!("[name='{s}']"),
-            Self::LinkText(s) => format!("a:contains('{s}')"),
-            Self::PartialLinkText(s) => format!("a[href*='{s}']"),
-            Self::ClassName(s) => format!(".{s}"),
         }
     }
 }
@@ -371,6 +488,85 @@ mod tests {
         assert!(matches!(id, Locator::Id(_)));
     }
 
+    #[test]
+    fn test_locator_combinators() {
+        let first = Locator::first(".message");
+        assert!(matches!(first, Locator::Nth(_, 0)));
+        assert_eq!(first.to_css_selector(), None);
+        assert!(first.elements_script("document").ends_with(".slice(0, 1)"));
+
+        let third = Locator::nth("li.item", 2);
+        assert!(matches!(third, Locator::Nth(_, 2)));
+
+        let nested = Locator::within(&Locator::id("chat"), &Locator::class("bot-message"));
+        assert_eq!(
+            nested.to_css_selector().as_deref(),
+            Some("#chat .bot-message")
+        );
+
+        let fanned = Locator::within(
+            &Locator::css(".chat, #messages"),
+            &Locator::css("p, span.text"),
+        );
+        assert_eq!(
+            fanned.to_css_selector().as_deref(),
+            Some(".chat p, .chat span.text, #messages p, #messages span.text")
+        );
+
+        let any = Locator::any(vec![
+            Locator::id("send"),
+            Locator::name("send"),
+            Locator::css("button[type='submit']"),
+        ]);
+        assert_eq!(
+            any.to_css_selector().as_deref(),
+            Some("#send, [name='send'], button[type='submit']")
+        );
+    }
+
+    #[test]
+    fn test_text_locator_builds_match_script() {
+        let logout = Locator::text("button", "Log 'out\"");
+        assert_eq!(logout.to_css_selector(), None);
+
+        let script = logout.match_script("document", "m1");
+        assert!(script.contains(r#"document.querySelectorAll("button")"#));
+        assert!(script.contains(r#"e.textContent.includes("Log 'out\"")"#));
+        assert!(script.contains(r#"setAttribute('data-bottest-match', "m1")"#));
+
+        assert!(Locator::css("button").to_css_selector().is_some());
+
+        let menu = Locator::any(vec![
+            Locator::css(".logout"),
+            Locator::text("button", "Logout"),
+        ]);
+        assert_eq!(menu.to_css_selector(), None);
+        let script = menu.match_script("document", "m2");
+        assert!(script.contains(r#"document.querySelectorAll(".logout")"#));
+        assert!(script.contains(r#"e.textContent.includes("Logout")"#));
+
+        let scoped = Locator::within(&Locator::id("chat"), &Locator::text("button", "End Chat"));
+        assert_eq!(scoped.to_css_selector(), None);
+        assert!(scoped
+            .elements_script("document")
+            .contains(r#"flatMap(p => [...p.querySelectorAll("button")]"#));
+    }
+
+    #[test]
+    fn test_locator_scripts_target_root_document() {
+        let frame_doc = Locator::nth("iframe", 1).element_expression("document");
+        assert_eq!(frame_doc, r#"document.querySelectorAll("iframe")[1]"#);
+        assert_eq!(
+            Locator::id("editor").element_expression("doc"),
+            r##"doc.querySelector("#editor")"##
+        );
+
+        let script = Locator::css("button.save").match_script("doc", "m2");
+        assert!(script.contains(r#"doc.querySelectorAll("button.save")"#));
+        assert!(!script.contains("filter"));
+        assert!(script.contains(r#"setAttribute('data-bottest-match', "m2")"#));
+    }
+
     #[test]
     fn test_action_chain() {
         let chain = ActionChain::new()
@@ -1,10 +1,12 @@
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::time::Duration;
 
 use super::browser::{Browser, Element};
 use super::Locator;
 
+const OIDC_REDIRECT_TIMEOUT: Duration = Duration::from_secs(15);
+
 #[async_trait::async_trait]
 pub trait Page {
     fn url_pattern(&self) -> &str;
@@ -15,6 +17,42 @@ pub trait Page {
     }
 
     async fn wait_for_load(&self, browser: &Browser) -> Result<()>;
+
+    async fn assert_current(&self, browser: &(dyn CurrentUrl + Sync)) -> Result<()> {
+        let actual = browser.current_url().await?;
+        if !actual.contains(self.url_pattern()) {
+            anyhow::bail!(
+                "Expected page URL matching '{}' but browser is at '{actual}'",
+                self.url_pattern()
+            );
+        }
+        Ok(())
+    }
+
+    async fn assert_loaded(&self, browser: &Browser, timeout: Duration) -> Result<()> {
+        match tokio::time::timeout(timeout, self.wait_for_load(browser)).await {
+            Ok(result) => result
+                .with_context(|| format!("Page '{}' failed to load", self.url_pattern()))?,
+            Err(_) => anyhow::bail!(
+                "Page '{}' did not load within {}ms",
+                self.url_pattern(),
+                timeout.as_millis()
+            ),
+        }
+        self.assert_current(browser).await
+    }
+}
+
+#[async_trait::async_trait]
+pub trait CurrentUrl {
+    async fn current_url(&self) -> Result<String>;
+}
+
+#[async_trait::async_trait]
+impl CurrentUrl for Browser {
+    async fn current_url(&self) -> Result<String> {
+        Self::current_url(self).await
+    }
 }
 
 
@@ -56,6 +94,21 @@ impl LoginPage {
         Locator::css(".error, .error-message, .alert-error, .alert-danger, [role='alert']")
     }
 
+    #[must_use]
+    pub fn oidc_login_name_input() -> Locator {
+        Locator::css("#loginName, input[name='loginName'], input[autocomplete='username']")
+    }
+
+    #[must_use]
+    pub fn oidc_password_input() -> Locator {
+        Locator::css("#password, input[name='password'], input[type='password']")
+    }
+
+    #[must_use]
+    pub fn oidc_submit_button() -> Locator {
+        Locator::css("#submit-button, button[type='submit'], input[type='submit']")
+    }
+
     pub async fn enter_email(&self, browser: &Browser, email: &str) -> Result<()> {
         browser.fill(Self::email_input(), email).await
     }
@@ -78,6 +131,61 @@ impl LoginPage {
         Ok(())
     }
 
+    pub async fn login_via_oidc(
+        &self,
+        browser: &Browser,
+        email: &str,
+        password: &str,
+    ) -> Result<()> {
+        self.navigate(browser).await?;
+        self.wait_for_url(browser, OIDC_REDIRECT_TIMEOUT, |url| {
+            !url.starts_with(&self.base_url)
+        })
+        .await
+        .context("App did not redirect to the OIDC login page")?;
+
+        browser.wait_for(Self::oidc_login_name_input()).await?;
+        browser.fill(Self::oidc_login_name_input(), email).await?;
+        if !browser.exists(Self::oidc_password_input()).await {
+            browser.click(Self::oidc_submit_button()).await?;
+            browser.wait_for(Self::oidc_password_input()).await?;
+        }
+        browser.fill(Self::oidc_password_input(), password).await?;
+        browser.click(Self::oidc_submit_button()).await?;
+
+        self.wait_for_url(browser, OIDC_REDIRECT_TIMEOUT, |url| {
+            url.starts_with(&self.base_url) && !url.contains(self.url_pattern())
+        })
+        .await
+        .context("OIDC provider did not redirect back to the app")?;
+        Ok(())
+    }
+
+    async fn wait_for_url<F>(
+        &self,
+        browser: &Browser,
+        timeout: Duration,
+        predicate: F,
+    ) -> Result<String>
+    where
+        F: Fn(&str) -> bool + Send,
+    {
+        let start = std::time::Instant::now();
+        let mut url = browser.current_url().await?;
+
+        while !predicate(&url) {
+            if start.elapsed() >= timeout {
+                anyhow::bail!(
+                    "Timed out after {}ms waiting for URL change, still at {url}",
+                    timeout.as_millis()
+                );
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            url = browser.current_url().await?;
+        }
+        Ok(url)
+    }
+
     pub async fn has_error(&self, browser: &Browser) -> bool {
         browser.exists(Self::error_message()).await
     }
@@ -134,7 +242,10 @@ impl DashboardPage {
 
     #[must_use]
     pub fn logout_button() -> Locator {
-        Locator::css(".logout, .logout-btn, #logout, a[href*='logout'], button:contains('Logout')")
+        Locator::any(vec![
+            Locator::css(".logout, .logout-btn, #logout, a[href*='logout']"),
+            Locator::text("button", "Logout"),
+        ])
     }
 
     pub async fn get_nav_items(&self, browser: &Browser) -> Result<Vec<Element>> {
@@ -258,6 +369,34 @@ impl ChatPage {
         anyhow::bail!("Timeout waiting for bot response")
     }
 
+    pub async fn send_and_wait(
+        &self,
+        browser: &Browser,
+        message: &str,
+        timeout: Duration,
+    ) -> Result<String> {
+        let before = self.get_bot_messages(browser).await.unwrap_or_default().len();
+        self.send_message(browser, message).await?;
+
+        let start = std::time::Instant::now();
+        while start.elapsed() < timeout {
+            if !self.is_typing(browser).await {
+                let messages = self.get_bot_messages(browser).await.unwrap_or_default();
+                if messages.len() > before {
+                    if let Some(last) = messages.last() {
+                        return Ok(last.clone());
+                    }
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        anyhow::bail!(
+            "No new bot message within {}ms after sending '{message}'",
+            timeout.as_millis()
+        )
+    }
+
     pub async fn get_bot_messages(&self, browser: &Browser) -> Result<Vec<String>> {
         let elements = browser.find_all(Self::bot_message()).await?;
         let mut messages = Vec::new();
@@ -347,7 +486,10 @@ impl QueuePage {
 
     #[must_use]
     pub fn take_next_button() -> Locator {
-        Locator::css(".take-next, #take-next, button:contains('Take Next')")
+        Locator::any(vec![
+            Locator::css(".take-next, #take-next"),
+            Locator::text("button", "Take Next"),
+        ])
     }
 
     pub async fn get_queue_count(&self, browser: &Browser) -> Result<u32> {
@@ -363,6 +505,10 @@ impl QueuePage {
     pub async fn take_next(&self, browser: &Browser) -> Result<()> {
         browser.click(Self::take_next_button()).await
     }
+
+    pub async fn can_take_next(&self, browser: &Browser) -> Result<bool> {
+        browser.is_element_enabled(Self::take_next_button()).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -378,6 +524,94 @@ impl Page for QueuePage {
 }
 
 
+pub struct AttendantChatPage {
+    pub base_url: String,
+}
+
+impl AttendantChatPage {
+    #[must_use]
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+        }
+    }
+
+    pub async fn navigate(&self, browser: &Browser) -> Result<()> {
+        browser.goto(&format!("{}/attendant", self.base_url)).await
+    }
+
+    #[must_use]
+    pub fn active_conversation() -> Locator {
+        Locator::css(".active-conversation, .conversation-panel, #active-conversation")
+    }
+
+    #[must_use]
+    pub fn customer_message() -> Locator {
+        Locator::css(".customer-message, .message-customer, [data-role='customer']")
+    }
+
+    #[must_use]
+    pub fn reply_input() -> Locator {
+        Locator::css(
+            "#reply-input, .reply-input, textarea[name='reply'], input[name='reply'], .agent-input",
+        )
+    }
+
+    #[must_use]
+    pub fn send_button() -> Locator {
+        Locator::css("#send-reply, .send-reply, .agent-send, button[type='submit']")
+    }
+
+    #[must_use]
+    pub fn end_chat_button() -> Locator {
+        Locator::any(vec![
+            Locator::css(".end-chat, #end-chat, .close-conversation"),
+            Locator::text("button", "End Chat"),
+        ])
+    }
+
+    pub async fn has_active_conversation(&self, browser: &Browser) -> bool {
+        browser.exists(Self::active_conversation()).await
+    }
+
+    pub async fn get_customer_messages(&self, browser: &Browser) -> Result<Vec<String>> {
+        let elements = browser.find_all(Self::customer_message()).await?;
+        let mut messages = Vec::new();
+        for elem in elements {
+            if let Ok(text) = elem.text().await {
+                messages.push(text);
+            }
+        }
+        Ok(messages)
+    }
+
+    pub async fn reply(&self, browser: &Browser, text: &str) -> Result<()> {
+        browser.fill(Self::reply_input(), text).await?;
+        browser.click(Self::send_button()).await?;
+        Ok(())
+    }
+
+    pub async fn end_chat(&self, browser: &Browser) -> Result<()> {
+        browser.click(Self::end_chat_button()).await?;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Page for AttendantChatPage {
+    fn url_pattern(&self) -> &'static str {
+        "/attendant"
+    }
+
+    async fn wait_for_load(&self, browser: &Browser) -> Result<()> {
+        browser.wait_for(Self::active_conversation()).await?;
+        browser.wait_for(Self::reply_input()).await?;
+        Ok(())
+    }
+}
+
+
 pub struct BotManagementPage {
     pub base_url: String,
 }
@@ -435,12 +669,15 @@ impl BotManagementPage {
     pub async fn create_bot(&self, browser: &Browser, name: &str, description: &str) -> Result<()> {
         self.click_create_bot(browser).await?;
         tokio::time::sleep(Duration::from_millis(300)).await;
-        browser.fill(Self::bot_name_input(), name).await?;
         browser
-            .fill(Self::bot_description_input(), description)
-            .await?;
-        browser.click(Self::save_button()).await?;
-        Ok(())
+            .submit_form(
+                &[
+                    (Self::bot_name_input(), name),
+                    (Self::bot_description_input(), description),
+                ],
+                Self::save_button(),
+            )
+            .await
     }
 
     pub async fn edit_bot(&self, browser: &Browser, bot_name: &str) -> Result<()> {
@@ -582,6 +819,17 @@ impl Page for AnalyticsPage {
 mod tests {
     use super::*;
 
+    struct FakeBrowser {
+        url: String,
+    }
+
+    #[async_trait::async_trait]
+    impl CurrentUrl for FakeBrowser {
+        async fn current_url(&self) -> Result<String> {
+            Ok(self.url.clone())
+        }
+    }
+
     #[test]
     fn test_login_page_locators() {
         let _ = LoginPage::email_input();
@@ -605,6 +853,32 @@ mod tests {
         let _ = QueuePage::take_next_button();
     }
 
+    #[test]
+    fn test_attendant_chat_page_locators() {
+        let _ = AttendantChatPage::active_conversation();
+        let _ = AttendantChatPage::customer_message();
+        let _ = AttendantChatPage::reply_input();
+        let _ = AttendantChatPage::send_button();
+        let _ = AttendantChatPage::end_chat_button();
+    }
+
+    #[tokio::test]
+    async fn test_assert_current_reports_expected_and_actual_url() {
+        let page = DashboardPage::new("http://localhost:4242");
+
+        let on_page = FakeBrowser {
+            url: "http://localhost:4242/dashboard".to_string(),
+        };
+        assert!(page.assert_current(&on_page).await.is_ok());
+
+        let elsewhere = FakeBrowser {
+            url: "http://localhost:4242/login?next=%2Fdashboard".to_string(),
+        };
+        let err = page.assert_current(&elsewhere).await.unwrap_err().to_string();
+        assert!(err.contains("'/dashboard'"));
+        assert!(err.contains("http://localhost:4242/login?next=%2Fdashboard"));
+    }
+
     #[test]
     fn test_page_url_patterns() {
         let login = LoginPage::new("http://localhost:4242");
@@ -619,6 +893,9 @@ mod tests {
         let queue = QueuePage::new("http://localhost:4242");
         assert_eq!(queue.url_pattern(), "/queue");
 
+        let attendant = AttendantChatPage::new("http://localhost:4242");
+        assert_eq!(attendant.url_pattern(), "/attendant");
+
         let bots = BotManagementPage::new("http://localhost:4242");
         assert_eq!(bots.url_pattern(), "/admin/bots");
     }
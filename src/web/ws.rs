@@ -0,0 +1,112 @@
+use anyhow::{Context, Result};
+use futures::{SinkExt, StreamExt};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::time::{timeout, Instant};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+pub struct WsClient {
+    url: String,
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl WsClient {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let (stream, _response) = connect_async(url)
+            .await
+            .with_context(|| format!("Failed to connect WebSocket to {url}"))?;
+
+        Ok(Self {
+            url: url.to_string(),
+            stream,
+        })
+    }
+
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub async fn send_text(&mut self, text: &str) -> Result<()> {
+        self.stream
+            .send(Message::Text(text.to_string()))
+            .await
+            .with_context(|| format!("Failed to send WebSocket message to {}", self.url))
+    }
+
+    pub async fn send_json(&mut self, value: &serde_json::Value) -> Result<()> {
+        self.send_text(&value.to_string()).await
+    }
+
+    pub async fn next_message(&mut self, wait: Duration) -> Result<String> {
+        let deadline = Instant::now() + wait;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let Ok(next) = timeout(remaining, self.stream.next()).await else {
+                anyhow::bail!(
+                    "No WebSocket message from {} within {}ms",
+                    self.url,
+                    wait.as_millis()
+                );
+            };
+
+            match next {
+                Some(Ok(Message::Text(text))) => return Ok(text.as_str().to_string()),
+                Some(Ok(Message::Binary(data))) => {
+                    return Ok(String::from_utf8_lossy(&data).into_owned())
+                }
+                Some(Ok(Message::Close(frame))) => {
+                    let reason = frame.map(|f| f.reason.to_string()).unwrap_or_default();
+                    anyhow::bail!("WebSocket {} closed by server: {reason}", self.url);
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    return Err(e).with_context(|| format!("WebSocket {} failed", self.url))
+                }
+                None => anyhow::bail!("WebSocket {} stream ended", self.url),
+            }
+        }
+    }
+
+    pub async fn next_json(&mut self, wait: Duration) -> Result<serde_json::Value> {
+        let text = self.next_message(wait).await?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("WebSocket message is not valid JSON: {text}"))
+    }
+
+    pub async fn collect_until<F>(
+        &mut self,
+        wait: Duration,
+        mut predicate: F,
+    ) -> Result<Vec<String>>
+    where
+        F: FnMut(&str) -> bool,
+    {
+        let deadline = Instant::now() + wait;
+        let mut collected = Vec::new();
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let message = self.next_message(remaining).await.with_context(|| {
+                format!(
+                    "Predicate not satisfied after {} WebSocket messages",
+                    collected.len()
+                )
+            })?;
+            let done = predicate(&message);
+            collected.push(message);
+            if done {
+                return Ok(collected);
+            }
+        }
+    }
+
+    pub async fn close(mut self) -> Result<()> {
+        self.stream
+            .close(None)
+            .await
+            .with_context(|| format!("Failed to close WebSocket {}", self.url))
+    }
+}
@@ -1,5 +1,6 @@
 use super::{check_webdriver_available, should_run_e2e_tests, E2ETestContext};
 use bottest::prelude::*;
+use bottest::web::pages::LoginPage;
 use bottest::web::WaitCondition;
 use bottest::web::{Browser, Locator};
 use std::time::Duration;
@@ -581,3 +582,75 @@ async fn test_unauthenticated_access_redirect() {
 
     ctx.close().await;
 }
+
+#[tokio::test]
+async fn test_login_via_oidc_redirect_lands_on_dashboard() {
+    if !should_run_e2e_tests() {
+        eprintln!("Skipping: E2E tests disabled");
+        return;
+    }
+
+    if !check_webdriver_available().await {
+        eprintln!("Skipping: WebDriver not available");
+        return;
+    }
+
+    let ctx = match E2ETestContext::setup_with_browser().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            return;
+        }
+    };
+
+    if !ctx.has_browser() {
+        eprintln!("Skipping: browser not available");
+        ctx.close().await;
+        return;
+    }
+
+    let email = "oidc-user@test.com";
+    let password = "OidcPass123!";
+
+    let Some(mock_zitadel) = ctx.ctx.mock_zitadel() else {
+        eprintln!("Skipping: MockZitadel not running");
+        ctx.close().await;
+        return;
+    };
+    setup_auth_mocks(&ctx.ctx, email, password).await;
+
+    let token_issued = reqwest::Client::new()
+        .post(format!("{}/oauth/v2/token", mock_zitadel.url()))
+        .form(&[
+            ("grant_type", "password"),
+            ("username", email),
+            ("password", password),
+        ])
+        .send()
+        .await
+        .map(|resp| resp.status().is_success())
+        .unwrap_or(false);
+    if !token_issued {
+        eprintln!("Skipping: MockZitadel did not issue a token");
+        ctx.close().await;
+        return;
+    }
+
+    let browser = ctx.browser.as_ref().unwrap();
+    let login_page = LoginPage::new(ctx.base_url());
+
+    if let Err(e) = login_page.login_via_oidc(browser, email, password).await {
+        eprintln!("✗ OIDC login failed: {}", e);
+        ctx.close().await;
+        panic!("OIDC login did not complete");
+    }
+
+    let current_url = browser.current_url().await.unwrap_or_default();
+    println!("✓ OIDC callback landed on {}", current_url);
+    assert!(
+        current_url.contains("/dashboard"),
+        "Expected dashboard URL after OIDC login, got {current_url}"
+    );
+
+    ctx.close().await;
+}
@@ -1,7 +1,40 @@
-use super::{should_run_e2e_tests, E2ETestContext};
+use super::{browser_config_for_port, should_run_e2e_tests, E2ETestContext};
 use anyhow::{bail, Result};
 use bottest::prelude::*;
-use bottest::web::Locator;
+use bottest::services::BrowserService;
+use bottest::web::pages::ChatPage;
+use bottest::web::{Browser, Locator};
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DELAYED_REPLY_FIXTURE: &str = r#"<!doctype html>
+<html>
+<body>
+  <div class="messages"></div>
+  <input id="chat-input" type="text">
+  <button id="send" type="button">Send</button>
+  <script>
+    document.getElementById('send').addEventListener('click', () => {
+      const input = document.getElementById('chat-input');
+      const text = input.value;
+      input.value = '';
+      if (text === 'silent') return;
+      const messages = document.querySelector('.messages');
+      const typing = document.createElement('div');
+      typing.className = 'typing-indicator';
+      messages.appendChild(typing);
+      setTimeout(() => {
+        typing.remove();
+        const reply = document.createElement('div');
+        reply.className = 'bot-message';
+        reply.textContent = 'You said: ' + text;
+        messages.appendChild(reply);
+      }, 800);
+    });
+  </script>
+</body>
+</html>"#;
 
 #[tokio::test]
 async fn test_chat_hi() -> Result<()> {
@@ -145,3 +178,107 @@ async fn test_chat_page_loads() -> Result<()> {
     ctx.close().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_send_and_wait_returns_delayed_reply() -> Result<()> {
+    if !should_run_e2e_tests() {
+        return Ok(());
+    }
+
+    let mut service = match BrowserService::start_on_free_port().await {
+        Ok(service) => service,
+        Err(e) => {
+            eprintln!("Skipping: browser not available: {e}");
+            return Ok(());
+        }
+    };
+    let browser = match Browser::new(browser_config_for_port(service.port())).await {
+        Ok(browser) => browser,
+        Err(e) => {
+            let _ = service.stop().await;
+            eprintln!("Skipping: failed to connect to browser: {e}");
+            return Ok(());
+        }
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/chat/test-bot"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "text/html")
+                .set_body_string(DELAYED_REPLY_FIXTURE),
+        )
+        .mount(&server)
+        .await;
+
+    let chat = ChatPage::new(&server.uri(), "test-bot");
+    chat.navigate(&browser).await?;
+
+    let reply = chat
+        .send_and_wait(&browser, "hello", Duration::from_secs(5))
+        .await;
+    let silent = chat
+        .send_and_wait(&browser, "silent", Duration::from_millis(1500))
+        .await;
+
+    let _ = browser.close();
+    let _ = service.stop().await;
+
+    assert_eq!(reply?, "You said: hello");
+    let err = silent.unwrap_err().to_string();
+    assert!(err.contains("No new bot message within 1500ms"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_chat_turn_against_stub_botserver() -> Result<()> {
+    if !should_run_e2e_tests() {
+        return Ok(());
+    }
+
+    let ctx = match E2ETestContext::setup_with_stub().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Skipping: failed to set up stub context: {e}");
+            return Ok(());
+        }
+    };
+    let Some(stub) = ctx.stub.as_ref() else {
+        bail!("setup_with_stub did not start a stub server");
+    };
+    stub.reply_to("refund", "Refunds take 5 business days.");
+
+    let mut service = match BrowserService::start_on_free_port().await {
+        Ok(service) => service,
+        Err(e) => {
+            eprintln!("Skipping: browser not available: {e}");
+            return Ok(());
+        }
+    };
+    let browser = match Browser::new(browser_config_for_port(service.port())).await {
+        Ok(browser) => browser,
+        Err(e) => {
+            let _ = service.stop().await;
+            eprintln!("Skipping: failed to connect to browser: {e}");
+            return Ok(());
+        }
+    };
+
+    let chat = ChatPage::new(ctx.base_url(), "support");
+    chat.navigate(&browser).await?;
+    let reply = chat
+        .send_and_wait(&browser, "How do I get a refund?", Duration::from_secs(5))
+        .await;
+    let turns = stub.chat_turns();
+
+    let _ = browser.close();
+    let _ = service.stop().await;
+    ctx.close().await;
+
+    assert_eq!(reply?, "Refunds take 5 business days.");
+    assert_eq!(turns.len(), 1);
+    assert_eq!(turns[0].bot, "support");
+    assert_eq!(turns[0].message, "How do I get a refund?");
+    Ok(())
+}
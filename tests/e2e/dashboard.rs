@@ -1,7 +1,116 @@
-use super::{should_run_e2e_tests, E2ETestContext};
+use super::{browser_config_for_port, should_run_e2e_tests, E2ETestContext};
 use bottest::prelude::*;
+use bottest::services::BrowserService;
+use bottest::web::pages::DashboardPage;
 use bottest::web::{Browser, Locator};
 use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEXT_BUTTONS_FIXTURE: &str = r#"<!doctype html>
+<html>
+<body>
+  <nav><a href="/dashboard">Home</a></nav>
+  <button id="settings">Settings</button>
+  <button id="sign-out" onclick="document.body.insertAdjacentHTML('beforeend', '<div id=logged-out></div>')">Logout</button>
+</body>
+</html>
+"#;
+
+const BOT_FORM_FIXTURE: &str = r#"<!doctype html>
+<html>
+<body>
+  <form id="bot-form" onsubmit="event.preventDefault(); document.getElementById('saved').textContent = [name.value, description.value, model.value].join('|');">
+    <input id="name" name="name">
+    <textarea id="description" name="description"></textarea>
+    <input id="model" name="model">
+    <button id="save" type="submit">Save</button>
+  </form>
+  <div id="saved"></div>
+</body>
+</html>
+"#;
+
+const MODEL_PICKER_FIXTURE: &str = r#"<!doctype html>
+<html>
+<body>
+  <select id="model" onchange="document.getElementById('changed').textContent = this.value">
+    <option value="gpt-4o-mini">GPT-4o mini</option>
+    <option value="claude-sonnet">Claude Sonnet</option>
+    <option value="llama-3">Llama 3</option>
+  </select>
+  <div id="changed"></div>
+</body>
+</html>
+"#;
+
+const DROP_ZONE_FIXTURE: &str = r#"<!doctype html>
+<html>
+<body>
+  <div id="drop-zone" style="width: 300px; height: 120px; border: 2px dashed #888;">Drop files here</div>
+  <ul id="dropped"></ul>
+  <script>
+    const zone = document.getElementById('drop-zone');
+    zone.addEventListener('dragover', (event) => event.preventDefault());
+    zone.addEventListener('drop', (event) => {
+      event.preventDefault();
+      for (const file of event.dataTransfer.files) {
+        const item = document.createElement('li');
+        item.textContent = `${file.name}:${file.size}:${file.type}`;
+        document.getElementById('dropped').appendChild(item);
+      }
+    });
+  </script>
+</body>
+</html>
+"#;
+
+const DELAYED_READY_FIXTURE: &str = r#"<!doctype html>
+<html>
+<body>
+  <div id="app">Loading...</div>
+  <script>
+    setTimeout(() => {
+      document.getElementById('app').textContent = 'Ready';
+      window.__APP_READY__ = true;
+    }, 800);
+  </script>
+</body>
+</html>
+"#;
+
+async fn start_fixture_browser() -> Option<(BrowserService, Browser)> {
+    let mut service = match BrowserService::start_on_free_port().await {
+        Ok(service) => service,
+        Err(e) => {
+            eprintln!("Skipping: browser not available: {e}");
+            return None;
+        }
+    };
+    let config = browser_config_for_port(service.port()).with_timeout(Duration::from_secs(5));
+    match Browser::new(config).await {
+        Ok(browser) => Some((service, browser)),
+        Err(e) => {
+            let _ = service.stop().await;
+            eprintln!("Skipping: failed to connect to browser: {e}");
+            None
+        }
+    }
+}
+
+async fn serve_fixture(route: &str, html: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(route))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "text/html")
+                .set_body_string(html),
+        )
+        .mount(&server)
+        .await;
+    server
+}
 
 fn admin_credentials() -> (String, String) {
     let email = std::env::var("TEST_ADMIN_EMAIL").unwrap_or_else(|_| "admin@test.com".to_string());
@@ -833,3 +942,201 @@ async fn test_mock_services_available() {
 
     ctx.close().await;
 }
+
+#[tokio::test]
+async fn test_text_locator_finds_button_by_visible_text() -> anyhow::Result<()> {
+    if !should_run_e2e_tests() {
+        return Ok(());
+    }
+
+    let mut service = match BrowserService::start_on_free_port().await {
+        Ok(service) => service,
+        Err(e) => {
+            eprintln!("Skipping: browser not available: {e}");
+            return Ok(());
+        }
+    };
+    let browser = match Browser::new(browser_config_for_port(service.port())).await {
+        Ok(browser) => browser,
+        Err(e) => {
+            let _ = service.stop().await;
+            eprintln!("Skipping: failed to connect to browser: {e}");
+            return Ok(());
+        }
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/dashboard"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "text/html")
+                .set_body_string(TEXT_BUTTONS_FIXTURE),
+        )
+        .mount(&server)
+        .await;
+
+    let dashboard = DashboardPage::new(&server.uri());
+    dashboard.navigate(&browser).await?;
+
+    let found = browser.find(Locator::text("button", "Logout")).await;
+    let found_id = match &found {
+        Ok(elem) => elem.attr("id").await?,
+        Err(_) => None,
+    };
+    let missing = browser
+        .exists(Locator::text("button", "Delete account"))
+        .await;
+    let logged_out = match dashboard.logout(&browser).await {
+        Ok(()) => browser.exists(Locator::id("logged-out")).await,
+        Err(_) => false,
+    };
+
+    let _ = browser.close();
+    let _ = service.stop().await;
+
+    assert_eq!(found_id.as_deref(), Some("sign-out"));
+    assert!(!missing);
+    assert!(logged_out);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fill_form_sets_every_field() -> anyhow::Result<()> {
+    if !should_run_e2e_tests() {
+        return Ok(());
+    }
+    let Some((mut service, browser)) = start_fixture_browser().await else {
+        return Ok(());
+    };
+
+    let server = serve_fixture("/bots/new", BOT_FORM_FIXTURE).await;
+    browser.goto(&format!("{}/bots/new", server.uri())).await?;
+
+    let fields = [
+        (Locator::id("name"), "support"),
+        (Locator::id("description"), "Answers billing questions"),
+        (Locator::id("model"), "gpt-4"),
+    ];
+    let submitted = browser.submit_form(&fields, Locator::id("save")).await;
+    let mut values = Vec::new();
+    for (locator, _) in &fields {
+        values.push(browser.find(locator.clone()).await?.value().await?);
+    }
+    let saved = browser.text(Locator::id("saved")).await?;
+    let missing = browser
+        .fill_form(&[
+            (Locator::id("name"), "support"),
+            (Locator::id("temperature"), "0.2"),
+        ])
+        .await;
+
+    let _ = browser.close();
+    let _ = service.stop().await;
+
+    submitted?;
+    assert_eq!(
+        values,
+        vec!["support", "Answers billing questions", "gpt-4"]
+    );
+    assert_eq!(saved, "support|Answers billing questions|gpt-4");
+    let err = format!("{:#}", missing.unwrap_err());
+    assert!(err.contains("form field #1"));
+    assert!(err.contains("temperature"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_select_option_by_text_and_value() -> anyhow::Result<()> {
+    if !should_run_e2e_tests() {
+        return Ok(());
+    }
+    let Some((mut service, browser)) = start_fixture_browser().await else {
+        return Ok(());
+    };
+
+    let server = serve_fixture("/settings", MODEL_PICKER_FIXTURE).await;
+    browser.goto(&format!("{}/settings", server.uri())).await?;
+
+    let initial = browser.selected_option(Locator::id("model")).await?;
+    let by_text = browser
+        .select_by_text(Locator::id("model"), "Claude Sonnet")
+        .await;
+    let after_text = browser.selected_option(Locator::id("model")).await?;
+    let changed = browser.text(Locator::id("changed")).await?;
+    let by_value = browser
+        .select_by_value(Locator::id("model"), "llama-3")
+        .await;
+    let after_value = browser.selected_option(Locator::id("model")).await?;
+    let unknown = browser
+        .select_by_text(Locator::id("model"), "Mistral")
+        .await;
+
+    let _ = browser.close();
+    let _ = service.stop().await;
+
+    by_text?;
+    by_value?;
+    assert_eq!(initial.as_deref(), Some("gpt-4o-mini"));
+    assert_eq!(after_text.as_deref(), Some("claude-sonnet"));
+    assert_eq!(changed, "claude-sonnet");
+    assert_eq!(after_value.as_deref(), Some("llama-3"));
+    assert!(unknown.unwrap_err().to_string().contains("Mistral"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_drag_file_onto_drop_zone() -> anyhow::Result<()> {
+    if !should_run_e2e_tests() {
+        return Ok(());
+    }
+    let Some((mut service, browser)) = start_fixture_browser().await else {
+        return Ok(());
+    };
+
+    let dir = std::path::PathBuf::from("./tmp").join(format!("bottest-drop-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+    let file = dir.join("faq.md");
+    std::fs::write(&file, "# FAQ\nRefunds take 5 days.\n")?;
+
+    let server = serve_fixture("/kb", DROP_ZONE_FIXTURE).await;
+    browser.goto(&format!("{}/kb", server.uri())).await?;
+
+    let dropped = browser.drag_file(Locator::id("drop-zone"), &file).await;
+    let listed = browser.text(Locator::css("#dropped li")).await;
+
+    let _ = browser.close();
+    let _ = service.stop().await;
+    let _ = std::fs::remove_dir_all(&dir);
+
+    dropped?;
+    assert_eq!(listed?, "faq.md:27:text/markdown");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_wait_for_app_ready_flag() -> anyhow::Result<()> {
+    if !should_run_e2e_tests() {
+        return Ok(());
+    }
+    let Some((mut service, browser)) = start_fixture_browser().await else {
+        return Ok(());
+    };
+
+    let server = serve_fixture("/app", DELAYED_READY_FIXTURE).await;
+    browser.goto(&format!("{}/app", server.uri())).await?;
+
+    let too_soon = browser
+        .wait_for_app_ready("window.__APP_READY__ === true", Duration::from_millis(100))
+        .await;
+    let ready = browser.wait_for_default_app_ready().await;
+    let text = browser.text(Locator::id("app")).await?;
+
+    let _ = browser.close();
+    let _ = service.stop().await;
+
+    assert!(too_soon.unwrap_err().to_string().contains("App not ready"));
+    ready?;
+    assert_eq!(text, "Ready");
+    Ok(())
+}
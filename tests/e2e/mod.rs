@@ -4,8 +4,9 @@ mod dashboard;
 mod platform_flow;
 
 use bottest::prelude::*;
-use bottest::services::{BrowserService, DEFAULT_DEBUG_PORT};
+use bottest::services::{ApiClient, BrowserService};
 use bottest::web::{Browser, BrowserConfig, BrowserType};
+use bottest::PortAllocator;
 use std::time::Duration;
 
 pub struct E2ETestContext {
@@ -13,29 +14,30 @@ pub struct E2ETestContext {
     pub server: BotServerInstance,
     pub ui: Option<BotUIInstance>,
     pub browser: Option<Browser>,
+    pub stub: Option<StubBotServer>,
     browser_service: Option<BrowserService>,
 }
 
 async fn is_service_running(url: &str) -> bool {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(2))
-        .danger_accept_invalid_certs(true)
-        .build()
-        .unwrap_or_default();
-
-    if let Ok(resp) = client.get(format!("{url}/health")).send().await {
-        if resp.status().is_success() {
-            return true;
-        }
+    let Ok(client) = ApiClient::with_timeout(url, Duration::from_secs(2)) else {
+        return false;
+    };
+
+    if client.is_healthy().await {
+        return true;
     }
-    if let Ok(resp) = client.get(url).send().await {
-        return resp.status().is_success() || resp.status().as_u16() == 200;
+    if let Ok(resp) = client.get("").await {
+        return resp.status().is_success();
     }
     false
 }
 
 impl E2ETestContext {
     pub async fn setup() -> anyhow::Result<Self> {
+        if std::env::var("BOTTEST_STUB_SERVER").is_ok() {
+            return Self::setup_with_stub().await;
+        }
+
         let botserver_url =
             std::env::var("BOTSERVER_URL").unwrap_or_else(|_| "https://localhost:9000".to_string());
         let botui_url =
@@ -80,6 +82,22 @@ impl E2ETestContext {
             server,
             ui,
             browser: None,
+            stub: None,
+            browser_service: None,
+        })
+    }
+
+    pub async fn setup_with_stub() -> anyhow::Result<Self> {
+        let ctx = TestHarness::with_existing_stack().await?;
+        let stub = StubBotServer::start(PortAllocator::allocate()).await?;
+        println!("🧪 Using stub BotServer at {}", stub.url());
+
+        Ok(Self {
+            ctx,
+            server: BotServerInstance::existing(&stub.url()),
+            ui: None,
+            browser: None,
+            stub: Some(stub),
             browser_service: None,
         })
     }
@@ -124,10 +142,25 @@ impl E2ETestContext {
             }
         };
 
-        let browser_service = match BrowserService::start(DEFAULT_DEBUG_PORT).await {
-            Ok(bs) => {
-                log::info!("Browser started with CDP on port {}", DEFAULT_DEBUG_PORT);
-                Some(bs)
+        let started = match cdp_port_override() {
+            Some(port) => BrowserService::start(port).await,
+            None => BrowserService::start_on_free_port().await,
+        };
+        let browser_service = match started {
+            Ok(mut bs) => {
+                if !bs.is_healthy().await {
+                    log::warn!("Browser CDP on port {} is unhealthy, restarting", bs.port());
+                    if let Err(e) = bs.restart().await {
+                        log::error!("Failed to restart browser: {}", e);
+                    }
+                }
+                if bs.is_healthy().await {
+                    log::info!("Browser started with CDP on port {}", bs.port());
+                    Some(bs)
+                } else {
+                    eprintln!("Browser CDP on port {} never became healthy", bs.port());
+                    None
+                }
             }
             Err(e) => {
                 log::error!("Failed to start browser: {}", e);
@@ -136,8 +169,8 @@ impl E2ETestContext {
             }
         };
 
-        let browser = if browser_service.is_some() {
-            let config = browser_config();
+        let browser = if let Some(bs) = &browser_service {
+            let config = browser_config_for_port(bs.port());
             match Browser::new(config).await {
                 Ok(b) => {
                     log::info!("Browser CDP connection established");
@@ -159,6 +192,7 @@ impl E2ETestContext {
             server,
             ui,
             browser,
+            stub: None,
             browser_service,
         })
     }
@@ -186,15 +220,19 @@ impl E2ETestContext {
         if let Some(mut bs) = self.browser_service.take() {
             let _ = bs.stop().await;
         }
+        if let Some(mut ui) = self.ui.take() {
+            let _ = ui.stop().await;
+        }
+        let _ = self.server.stop().await;
     }
 }
 
-pub fn browser_config() -> BrowserConfig {
+fn cdp_port_override() -> Option<u16> {
+    std::env::var("CDP_PORT").ok().and_then(|p| p.parse().ok())
+}
+
+pub fn browser_config_for_port(debug_port: u16) -> BrowserConfig {
     let headless = std::env::var("HEADLESS").is_ok();
-    let debug_port = std::env::var("CDP_PORT")
-        .ok()
-        .and_then(|p| p.parse().ok())
-        .unwrap_or(DEFAULT_DEBUG_PORT);
 
     BrowserConfig::default()
         .with_browser(BrowserType::Chrome)
@@ -288,11 +326,10 @@ async fn test_harness_starts_server() {
         }
     };
 
-    if server.is_running() {
-        let client = reqwest::Client::new();
-        let health_url = format!("{}/health", server.url);
+    if server.is_running().await {
+        let client = ApiClient::new(&server.url).unwrap();
 
-        if let Ok(resp) = client.get(&health_url).send().await {
+        if let Ok(resp) = client.get("/health").await {
             assert!(resp.status().is_success());
         }
     }
@@ -351,13 +388,9 @@ async fn test_existing_stack_connection() {
 
     match E2ETestContext::setup().await {
         Ok(ctx) => {
-            let client = reqwest::Client::builder()
-                .danger_accept_invalid_certs(true)
-                .build()
-                .unwrap();
+            let client = ApiClient::new(ctx.api_url()).unwrap();
 
-            let health_url = format!("{}/health", ctx.api_url());
-            match client.get(&health_url).send().await {
+            match client.get("/health").await {
                 Ok(resp) => {
                     if resp.status().is_success() {
                         println!("✓ Connected to existing botserver at {}", ctx.api_url());
@@ -34,7 +34,7 @@ pub async fn verify_platform_loading(ctx: &E2ETestContext) -> anyhow::Result<()>
 pub async fn verify_botserver_running(ctx: &E2ETestContext) -> anyhow::Result<()> {
     let client = reqwest::Client::new();
 
-    assert!(ctx.server.is_running(), "BotServer process is not running");
+    assert!(ctx.server.is_running().await, "BotServer process is not running");
 
     println!("✓ BotServer process running");
 
@@ -342,7 +342,7 @@ async fn test_botserver_startup() {
         }
     };
 
-    if !ctx.server.is_running() {
+    if !ctx.server.is_running().await {
         eprintln!("Skipping: BotServer not running (BOTSERVER_BIN not set or binary not found)");
         ctx.close().await;
         return;
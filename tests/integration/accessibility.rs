@@ -31,7 +31,7 @@ async fn get_test_server() -> Option<(Option<TestContext>, String)> {
     let ctx = TestHarness::quick().await.ok()?;
     let server = ctx.start_botserver().await.ok()?;
 
-    if server.is_running() {
+    if server.is_running().await {
         Some((Some(ctx), server.url.clone()))
     } else {
         None
@@ -0,0 +1,81 @@
+use bottest::prelude::*;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_reply(server: &MockServer, message: &str, reply: serde_json::Value) {
+    Mock::given(method("POST"))
+        .and(path("/api/bots/support/chat"))
+        .and(body_partial_json(json!({ "message": message })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(reply))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_conversation_runner_records_turns() {
+    let server = MockServer::start().await;
+    mount_reply(
+        &server,
+        "hi",
+        json!({
+            "content": "Hello! How can I help?",
+            "quick_replies": ["Sales", "Support"]
+        }),
+    )
+    .await;
+    mount_reply(
+        &server,
+        "support",
+        json!({ "content": "Connecting you to an agent" }),
+    )
+    .await;
+
+    let mut runner = ConversationRunner::new(&server.uri(), "support");
+
+    let reply = runner.send("hi").await.unwrap();
+    assert_eq!(reply.quick_replies, vec!["Sales", "Support"]);
+    runner
+        .expect_reply_contains("How can I help")
+        .expect_quick_replies(&["Sales", "Support"]);
+
+    runner.send("support").await.unwrap();
+    runner
+        .expect_reply_contains("agent")
+        .expect_quick_replies(&["Billing"]);
+
+    let record = runner.finish();
+
+    assert_eq!(record.bot_name, "support");
+    assert_eq!(record.messages.len(), 4);
+    assert_eq!(record.messages[0].direction, MessageDirection::Incoming);
+    assert_eq!(record.messages[0].content, "hi");
+    assert_eq!(record.messages[1].direction, MessageDirection::Outgoing);
+    assert_eq!(record.messages[1].content, "Hello! How can I help?");
+    assert!(record.messages[1].latency_ms.is_some());
+    assert!(record.messages[0].latency_ms.is_none());
+
+    assert_eq!(record.assertions.len(), 4);
+    assert!(!record.passed);
+    let failed: Vec<&AssertionRecord> = record.assertions.iter().filter(|a| !a.passed).collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].assertion_type, "quick_replies");
+    assert!(failed[0].message.contains("Billing"));
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert!(body.get("session_id").is_some());
+}
+
+#[tokio::test]
+async fn test_conversation_runner_reports_http_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let mut runner = ConversationRunner::new(&server.uri(), "support");
+    let err = runner.send("hi").await.unwrap_err();
+    assert!(err.to_string().contains("503"));
+}
@@ -511,3 +511,467 @@ async fn test_session_and_message_fixtures() {
         assert_eq!(messages[0].content, "Hello from test");
     }
 }
+
+#[tokio::test]
+async fn test_snapshot_restore_discards_rows() {
+    let ctx = match TestHarness::database_only().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            return;
+        }
+    };
+
+    let Some(pg) = ctx.postgres() else {
+        eprintln!("Skipping: no managed PostgreSQL instance");
+        return;
+    };
+
+    pg.execute("CREATE TABLE snapshot_probe (id INTEGER PRIMARY KEY, label TEXT)")
+        .unwrap();
+    let baseline = pg.snapshot().unwrap();
+
+    pg.execute("INSERT INTO snapshot_probe (id, label) VALUES (1, 'after snapshot')")
+        .unwrap();
+    assert_eq!(
+        pg.query("SELECT COUNT(*) FROM snapshot_probe").unwrap(),
+        "1"
+    );
+
+    pg.restore(&baseline).unwrap();
+    assert_eq!(
+        pg.query("SELECT COUNT(*) FROM snapshot_probe").unwrap(),
+        "0"
+    );
+
+    pg.drop_snapshot(baseline).unwrap();
+}
+
+#[tokio::test]
+async fn test_with_transaction_rolls_back() {
+    let ctx = match TestHarness::database_only().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            return;
+        }
+    };
+
+    use diesel::prelude::*;
+    use diesel::sql_query;
+    use diesel::sql_types::Uuid as DieselUuid;
+
+    #[derive(QueryableByName)]
+    struct UserCount {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        count: i64,
+    }
+
+    let user = admin_user();
+    let count_query = "SELECT COUNT(*) as count FROM users WHERE id = $1";
+
+    let tx_user = user.clone();
+    let inserted = ctx
+        .with_transaction(|conn| {
+            Box::pin(async move {
+                tx_user.insert_with(conn)?;
+                let visible: Vec<UserCount> = sql_query(count_query)
+                    .bind::<DieselUuid, _>(tx_user.id)
+                    .load(conn)?;
+                Ok(visible[0].count)
+            })
+        })
+        .await;
+
+    let inserted = match inserted {
+        Ok(count) => count,
+        Err(e) => {
+            eprintln!("Skipping transaction test (table may not exist): {}", e);
+            return;
+        }
+    };
+    assert_eq!(inserted, 1);
+
+    let pool = ctx.db_pool().await.unwrap();
+    let mut conn = pool.get().unwrap();
+    let after: Vec<UserCount> = sql_query(count_query)
+        .bind::<DieselUuid, _>(user.id)
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(after[0].count, 0);
+}
+
+#[tokio::test]
+async fn test_count_find_and_delete_helpers() {
+    let ctx = match TestHarness::database_only().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = ctx.delete_all("customers").await {
+        eprintln!("Skipping query helper test (table may not exist): {}", e);
+        return;
+    }
+
+    let first = customer("+15550000001");
+    let second = customer("+15550000002");
+    ctx.insert_customer(&first).await.unwrap();
+    ctx.insert_customer(&second).await.unwrap();
+
+    assert_eq!(ctx.count("customers").await.unwrap(), 2);
+
+    use diesel::prelude::*;
+
+    #[derive(QueryableByName)]
+    struct CustomerRow {
+        #[diesel(sql_type = diesel::sql_types::Uuid)]
+        id: Uuid,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        phone: Option<String>,
+    }
+
+    let found: Option<CustomerRow> = ctx.find_by_id("customers", first.id).await.unwrap();
+    let found = found.expect("customer should exist");
+    assert_eq!(found.id, first.id);
+    assert_eq!(found.phone.as_deref(), Some("+15550000001"));
+
+    let missing: Option<CustomerRow> = ctx.find_by_id("customers", Uuid::new_v4()).await.unwrap();
+    assert!(missing.is_none());
+
+    assert_eq!(ctx.delete_all("customers").await.unwrap(), 2);
+    assert_eq!(ctx.count("customers").await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_insert_scenario_referential_integrity() {
+    let ctx = match TestHarness::database_only().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            return;
+        }
+    };
+
+    let scenario = Scenario::new()
+        .bot("sales")
+        .customer_on(Channel::WhatsApp)
+        .with_session()
+        .with_messages(["hi", "help"]);
+
+    if let Err(e) = ctx.insert_scenario(&scenario).await {
+        eprintln!("Skipping scenario test (tables may not exist): {}", e);
+        return;
+    }
+
+    use diesel::prelude::*;
+    use diesel::sql_query;
+    use diesel::sql_types::Uuid as DieselUuid;
+
+    #[derive(QueryableByName)]
+    struct LinkedMessage {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        content: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        bot_name: String,
+        #[diesel(sql_type = DieselUuid)]
+        customer_id: Uuid,
+    }
+
+    let pool = ctx.db_pool().await.unwrap();
+    let mut conn = pool.get().unwrap();
+    let rows: Vec<LinkedMessage> = sql_query(
+        "SELECT m.content, b.name AS bot_name, c.id AS customer_id
+         FROM messages m
+         JOIN sessions s ON s.id = m.session_id
+         JOIN bots b ON b.id = s.bot_id
+         JOIN customers c ON c.id = s.customer_id
+         WHERE s.id = $1
+         ORDER BY m.content DESC",
+    )
+    .bind::<DieselUuid, _>(scenario.session().id)
+    .load(&mut conn)
+    .unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].content, "hi");
+    assert_eq!(rows[1].content, "help");
+    assert!(rows
+        .iter()
+        .all(|r| r.bot_name == "sales" && r.customer_id == scenario.customer.id));
+}
+
+#[tokio::test]
+async fn test_migrate_up_and_roll_back() {
+    if std::process::Command::new("diesel")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        eprintln!("Skipping: diesel CLI not available");
+        return;
+    }
+
+    let root =
+        std::path::PathBuf::from("./tmp").join(format!("bottest-migrate-{}", Uuid::new_v4()));
+    let migrations = root.join("migrations");
+    for (version, up, down) in [
+        (
+            "2024-01-01-000000_create_widgets",
+            "CREATE TABLE widgets (id SERIAL PRIMARY KEY, name TEXT NOT NULL);",
+            "DROP TABLE widgets;",
+        ),
+        (
+            "2024-01-02-000000_add_widget_color",
+            "ALTER TABLE widgets ADD COLUMN color TEXT;",
+            "ALTER TABLE widgets DROP COLUMN color;",
+        ),
+    ] {
+        let dir = migrations.join(version);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("up.sql"), up).unwrap();
+        std::fs::write(dir.join("down.sql"), down).unwrap();
+    }
+
+    let pg =
+        match PostgresService::start(bottest::PortAllocator::allocate(), root.to_str().unwrap())
+            .await
+        {
+            Ok(pg) => pg.with_migration_dir(&migrations),
+            Err(e) => {
+                eprintln!("Skipping: {}", e);
+                return;
+            }
+        };
+
+    pg.migrate_to("20240101000000").unwrap();
+    assert_eq!(pg.applied_migrations().unwrap(), vec!["20240101000000"]);
+
+    pg.execute("INSERT INTO widgets (name) VALUES ('sprocket')")
+        .unwrap();
+    assert_eq!(pg.query("SELECT name FROM widgets").unwrap(), "sprocket");
+
+    pg.rollback(1).unwrap();
+    assert!(pg.applied_migrations().unwrap().is_empty());
+    assert_eq!(
+        pg.query("SELECT to_regclass('widgets') IS NULL").unwrap(),
+        "t"
+    );
+
+    pg.migrate_to("20240102000000").unwrap();
+    pg.reset_schema().unwrap();
+    assert!(pg.applied_migrations().unwrap().is_empty());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[tokio::test]
+async fn test_db_pool_exhaustion_times_out() {
+    let config = TestConfig::builder().postgres(true).db_pool_size(2).build();
+    let ctx = match TestHarness::setup(config).await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            return;
+        }
+    };
+
+    assert!(ctx
+        .db_pool_sized(0)
+        .unwrap_err()
+        .to_string()
+        .contains("at least 1"));
+
+    let pool = ctx.db_pool().await.unwrap();
+    assert_eq!(pool.max_size(), 2);
+
+    let first = pool.get().unwrap();
+    let second = pool.get().unwrap();
+    assert!(pool
+        .get_timeout(std::time::Duration::from_millis(200))
+        .is_err());
+
+    drop(first);
+    assert!(pool.get_timeout(std::time::Duration::from_secs(5)).is_ok());
+    drop(second);
+}
+
+#[tokio::test]
+async fn test_wait_for_row_sees_delayed_insert() {
+    let ctx = match TestHarness::database_only().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            return;
+        }
+    };
+
+    use diesel::prelude::*;
+    use diesel::sql_query;
+
+    let pool = ctx.db_pool().await.unwrap().clone();
+    sql_query("CREATE TABLE delayed_events (id SERIAL PRIMARY KEY, kind TEXT NOT NULL)")
+        .execute(&mut pool.get().unwrap())
+        .unwrap();
+
+    let err = ctx
+        .wait_for_row(
+            "delayed_events",
+            "kind = 'delivered'",
+            std::time::Duration::from_millis(200),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("No row in delayed_events"));
+
+    let writer = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        sql_query("INSERT INTO delayed_events (kind) VALUES ('delivered')")
+            .execute(&mut pool.get().unwrap())
+            .unwrap();
+    });
+
+    ctx.wait_for_row(
+        "delayed_events",
+        "kind = 'delivered'",
+        std::time::Duration::from_secs(5),
+    )
+    .await
+    .unwrap();
+    assert_eq!(ctx.count("delayed_events").await.unwrap(), 1);
+    writer.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_insert_all_uses_one_transaction() {
+    let ctx = match TestHarness::database_only().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = ctx.delete_all("customers").await {
+        eprintln!("Skipping batch insert test (table may not exist): {}", e);
+        return;
+    }
+
+    let customers: Vec<Customer> = (0..100)
+        .map(|i| customer(&format!("+1555100{i:04}")))
+        .collect();
+    let batch: Vec<&dyn Insertable> = customers.iter().map(|c| c as &dyn Insertable).collect();
+    ctx.insert_all(&batch).await.unwrap();
+    assert_eq!(ctx.count("customers").await.unwrap(), 100);
+
+    use diesel::{sql_query, QueryableByName, RunQueryDsl};
+
+    #[derive(QueryableByName)]
+    struct TxCount {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        count: i64,
+    }
+
+    let pool = ctx.db_pool().await.unwrap();
+    let rows: Vec<TxCount> = sql_query("SELECT COUNT(DISTINCT xmin::text) AS count FROM customers")
+        .load(&mut pool.get().unwrap())
+        .unwrap();
+    assert_eq!(rows[0].count, 1);
+
+    struct Rejected;
+
+    impl Insertable for Rejected {
+        fn insert(&self, _pool: &DbPool) -> anyhow::Result<()> {
+            anyhow::bail!("rejected")
+        }
+
+        fn insert_with(&self, _conn: &mut diesel::PgConnection) -> anyhow::Result<()> {
+            anyhow::bail!("rejected")
+        }
+    }
+
+    let fresh = customer("+15552000000");
+    assert!(ctx.insert_all(&[&fresh, &Rejected]).await.is_err());
+    assert_eq!(ctx.count("customers").await.unwrap(), 100);
+}
+
+#[tokio::test]
+async fn test_bot_config_round_trips_as_jsonb() {
+    let ctx = match TestHarness::database_only().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            return;
+        }
+    };
+
+    let mut bot = basic_bot("configured");
+    bot.config.insert(
+        "llm".to_string(),
+        json!({"model": "gpt-4", "temperature": 0.2}),
+    );
+    bot.config
+        .insert("channels".to_string(), json!(["whatsapp", "teams"]));
+
+    if let Err(e) = ctx.insert_bot(&bot).await {
+        eprintln!("Skipping bot config test (table may not exist): {}", e);
+        return;
+    }
+
+    use diesel::prelude::*;
+    use diesel::sql_query;
+    use diesel::sql_types::{Jsonb, Uuid as DieselUuid};
+
+    #[derive(QueryableByName)]
+    struct ConfigRow {
+        #[diesel(sql_type = Jsonb)]
+        config: serde_json::Value,
+    }
+
+    let pool = ctx.db_pool().await.unwrap();
+    let rows: Vec<ConfigRow> = sql_query("SELECT config FROM bots WHERE id = $1")
+        .bind::<DieselUuid, _>(bot.id)
+        .load(&mut pool.get().unwrap())
+        .unwrap();
+
+    assert_eq!(rows[0].config["llm"]["model"], "gpt-4");
+    assert_eq!(rows[0].config["channels"], json!(["whatsapp", "teams"]));
+}
+
+#[tokio::test]
+async fn test_user_and_customer_metadata_round_trip() {
+    let ctx = match TestHarness::database_only().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            return;
+        }
+    };
+
+    let mut user = regular_user();
+    user.metadata
+        .insert("beta_dashboard".to_string(), "enabled".to_string());
+    user.metadata
+        .insert("locale".to_string(), "pt-BR".to_string());
+
+    if let Err(e) = ctx.insert_user(&user).await {
+        eprintln!("Skipping metadata test (table may not exist): {}", e);
+        return;
+    }
+
+    let stored = ctx.user_metadata(user.id).await.unwrap();
+    assert_eq!(stored, user.metadata);
+    assert_eq!(stored.len(), 2);
+
+    let mut vip = customer("+15553000000");
+    vip.metadata.insert("tier".to_string(), "gold".to_string());
+    ctx.insert_customer(&vip).await.unwrap();
+    assert_eq!(
+        ctx.customer_metadata(vip.id).await.unwrap().get("tier"),
+        Some(&"gold".to_string())
+    );
+
+    assert!(ctx.user_metadata(Uuid::new_v4()).await.is_err());
+}
@@ -4,8 +4,11 @@ mod database;
 mod security;
 mod performance;
 mod compliance;
+mod conversation;
 mod accessibility;
 mod internationalization;
+mod storage;
+mod websocket;
 
 use bottest::prelude::*;
 
@@ -0,0 +1,96 @@
+use bottest::prelude::*;
+
+async fn setup_minio_test() -> Option<TestContext> {
+    match TestHarness::setup(TestConfig::storage_only()).await {
+        Ok(ctx) => Some(ctx),
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            None
+        }
+    }
+}
+
+async fn setup_redis_test() -> Option<TestContext> {
+    let config = TestConfig::builder().redis(true).build();
+
+    match TestHarness::setup(config).await {
+        Ok(ctx) => Some(ctx),
+        Err(e) => {
+            eprintln!("Skipping: {}", e);
+            None
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_minio_bucket_upload_and_list() {
+    let Some(ctx) = setup_minio_test().await else {
+        return;
+    };
+    let minio = ctx.minio().expect("MinIO should be running");
+
+    minio.ensure_bucket("kb-documents").await.unwrap();
+    minio.ensure_bucket("kb-documents").await.unwrap();
+    assert!(minio.bucket_exists("kb-documents").await.unwrap());
+
+    minio
+        .put_object("kb-documents", "faq/shipping.md", b"Shipping takes 3 days")
+        .await
+        .unwrap();
+    minio
+        .put_object("kb-documents", "manual.txt", b"Product manual")
+        .await
+        .unwrap();
+
+    let content = minio
+        .get_object("kb-documents", "faq/shipping.md")
+        .await
+        .unwrap();
+    assert_eq!(content, b"Shipping takes 3 days");
+
+    let mut objects = minio.list_objects("kb-documents", None).await.unwrap();
+    objects.sort();
+    assert_eq!(objects, vec!["faq/shipping.md", "manual.txt"]);
+
+    let faq = minio
+        .list_objects("kb-documents", Some("faq/"))
+        .await
+        .unwrap();
+    assert_eq!(faq, vec!["faq/shipping.md"]);
+}
+
+#[tokio::test]
+async fn test_redis_seed_and_inspect() {
+    let Some(ctx) = setup_redis_test().await else {
+        return;
+    };
+    let redis = ctx.redis().expect("Redis should be running");
+
+    redis.flushall().await.unwrap();
+    redis.set("session:abc:state", "active").await.unwrap();
+    redis
+        .seed(&[("queue:web:1", "customer-1"), ("queue:web:2", "customer-2")])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        redis.get("session:abc:state").await.unwrap(),
+        Some("active".to_string())
+    );
+    redis
+        .assert_key_eq("session:abc:state", "active")
+        .await
+        .unwrap();
+    assert!(redis
+        .assert_key_eq("session:abc:state", "ended")
+        .await
+        .is_err());
+
+    let mut queue_keys = redis.keys("queue:web:*").await.unwrap();
+    queue_keys.sort();
+    assert_eq!(queue_keys, vec!["queue:web:1", "queue:web:2"]);
+
+    redis.flushall().await.unwrap();
+    assert!(redis.keys("*").await.unwrap().is_empty());
+    assert_eq!(redis.get("session:abc:state").await.unwrap(), None);
+}
@@ -0,0 +1,90 @@
+use bottest::web::WsClient;
+use futures::{SinkExt, StreamExt};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+async fn start_echo_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let Ok(mut ws) = tokio_tungstenite::accept_async(socket).await else {
+                    return;
+                };
+                while let Some(Ok(message)) = ws.next().await {
+                    let Message::Text(text) = message else {
+                        continue;
+                    };
+                    let replies: Vec<String> = if text.as_str() == "stream" {
+                        vec![
+                            r#"{"type":"typing","active":true}"#.to_string(),
+                            r#"{"type":"token","text":"Hel"}"#.to_string(),
+                            r#"{"type":"token","text":"lo"}"#.to_string(),
+                            r#"{"type":"done"}"#.to_string(),
+                        ]
+                    } else {
+                        vec![format!("echo: {}", text.as_str())]
+                    };
+                    for reply in replies {
+                        if ws.send(Message::Text(reply)).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    format!("ws://{addr}")
+}
+
+#[tokio::test]
+async fn test_ws_client_round_trip() {
+    let url = start_echo_server().await;
+    let mut client = WsClient::connect(&url).await.unwrap();
+
+    client.send_text("hello").await.unwrap();
+    let reply = client.next_message(Duration::from_secs(5)).await.unwrap();
+    assert_eq!(reply, "echo: hello");
+
+    client
+        .send_json(&serde_json::json!({"message": "hi"}))
+        .await
+        .unwrap();
+    let reply = client.next_message(Duration::from_secs(5)).await.unwrap();
+    assert_eq!(reply, r#"echo: {"message":"hi"}"#);
+
+    client.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_ws_client_collects_streamed_tokens() {
+    let url = start_echo_server().await;
+    let mut client = WsClient::connect(&url).await.unwrap();
+
+    client.send_text("stream").await.unwrap();
+    let first = client.next_json(Duration::from_secs(5)).await.unwrap();
+    assert_eq!(first["type"], "typing");
+
+    let messages = client
+        .collect_until(Duration::from_secs(5), |m| m.contains(r#""done""#))
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 3);
+
+    let streamed: String = messages
+        .iter()
+        .filter_map(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .filter_map(|v| v["text"].as_str().map(str::to_string))
+        .collect();
+    assert_eq!(streamed, "Hello");
+
+    let err = client
+        .next_message(Duration::from_millis(200))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("No WebSocket message"));
+}